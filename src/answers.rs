@@ -0,0 +1,131 @@
+use crate::Answer;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The checked-in answers file, `answers.txt`.
+pub fn default_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.txt")
+}
+
+/// Expected answers for the real inputs, keyed by `(day, part)`.
+#[derive(Debug, Default)]
+pub struct KnownAnswers {
+    answers: HashMap<(u32, u32), String>,
+}
+
+impl KnownAnswers {
+    /// Parses lines of `<day> <part> <answer>`. Blank lines and `#` comments are ignored.
+    pub fn parse(text: &str) -> Result<KnownAnswers, String> {
+        KnownAnswers::parse_with(text, None)
+    }
+
+    /// Parses one day's answers, as lines of `<part> <answer>`.
+    pub fn parse_for_day(text: &str, day: u32) -> Result<KnownAnswers, String> {
+        KnownAnswers::parse_with(text, Some(day))
+    }
+
+    fn parse_with(text: &str, only_day: Option<u32>) -> Result<KnownAnswers, String> {
+        let mut answers = HashMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (day, rest) = match only_day {
+                Some(day) => (day, line),
+                None => {
+                    let Some((day, rest)) = line.split_once(char::is_whitespace) else {
+                        return Err(format!("line {}: expected <day> <part> <answer>", i + 1));
+                    };
+                    let day: u32 = day
+                        .parse()
+                        .map_err(|_| format!("line {}: bad day {day:?}", i + 1))?;
+                    (day, rest.trim_start())
+                }
+            };
+            let Some((part, answer)) = rest.split_once(char::is_whitespace) else {
+                return Err(format!("line {}: expected <part> <answer>", i + 1));
+            };
+            let part: u32 = match part.parse() {
+                Ok(part @ (1 | 2)) => part,
+                _ => return Err(format!("line {}: bad part {part:?}", i + 1)),
+            };
+            answers.insert((day, part), answer.trim().to_string());
+        }
+        Ok(KnownAnswers { answers })
+    }
+
+    pub fn load(path: &Path) -> io::Result<KnownAnswers> {
+        let text = fs::read_to_string(path)?;
+        KnownAnswers::parse(&text).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {err}", path.display()),
+            )
+        })
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn check(&self, day: u32, part: u32, actual: &Answer) -> Verdict {
+        match self.get(day, part) {
+            None => Verdict::Missing,
+            Some(expected) if expected == actual.to_string() => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            },
+        }
+    }
+}
+
+/// The outcome of checking one part against its known answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// No answer has been recorded for this part.
+    Missing,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {expected}, got {actual})")
+            }
+            Verdict::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+#[test]
+fn test_known_answers() {
+    let answers = KnownAnswers::parse("# comment\n1 1 11\n\n17 1 4,6,3\n").unwrap();
+    assert_eq!(answers.get(1, 1), Some("11"));
+    assert_eq!(answers.get(17, 1), Some("4,6,3"));
+    assert_eq!(answers.check(1, 1, &Answer::Int(11)), Verdict::Pass);
+    assert_eq!(
+        answers.check(1, 1, &Answer::Int(12)),
+        Verdict::Fail {
+            expected: "11".to_string(),
+            actual: "12".to_string()
+        }
+    );
+    assert_eq!(answers.check(1, 2, &Answer::Int(31)), Verdict::Missing);
+    assert!(KnownAnswers::parse("1 3 11").is_err());
+    assert!(KnownAnswers::parse("1 1").is_err());
+
+    let answers = KnownAnswers::parse_for_day("1 2028\n2 9021\n", 15).unwrap();
+    assert_eq!(answers.get(15, 2), Some("9021"));
+    assert!(KnownAnswers::parse_for_day("3 1", 15).is_err());
+}
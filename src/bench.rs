@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// A median this much slower than the baseline's is reported as a regression.
+pub const REGRESSION_THRESHOLD: f64 = 1.2;
+
+/// Below this a median is mostly timer noise, so a change is shown in nanoseconds rather than as
+/// a percentage, which would be huge (or infinite, against a 0 ns baseline) for a tiny change.
+pub const NOISE_FLOOR: Duration = Duration::from_micros(1);
+
+/// Where `aoc bench --save` keeps its results. Timings are machine-specific, so it isn't checked
+/// in.
+pub fn default_baseline_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("bench_baseline.txt")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        assert!(!samples.is_empty(), "no samples");
+        samples.sort();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        Stats {
+            min: samples[0],
+            median,
+            mean: samples.iter().sum::<Duration>() / n as u32,
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:>10.2?}  median {:>10.2?}  mean {:>10.2?}",
+            self.min, self.median, self.mean
+        )
+    }
+}
+
+#[test]
+fn test_stats() {
+    let ms = Duration::from_millis;
+    let stats = Stats::from_samples(&mut [ms(5), ms(1), ms(3)]);
+    assert_eq!(
+        stats,
+        Stats {
+            min: ms(1),
+            median: ms(3),
+            mean: ms(3)
+        }
+    );
+    let stats = Stats::from_samples(&mut [ms(4), ms(1), ms(2), ms(9)]);
+    assert_eq!(stats.median, ms(3));
+    assert_eq!(stats.mean, ms(4));
+}
+
+/// Times `iterations` calls of `f`.
+pub fn measure<T>(iterations: usize, mut f: impl FnMut() -> T) -> Stats {
+    let mut samples: Vec<Duration> = (0..iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Stats::from_samples(&mut samples)
+}
+
+/// Median timings from an earlier run, keyed by day and stage (`parse`, `part1` or `part2`).
+#[derive(Debug, Default)]
+pub struct Baseline {
+    medians: BTreeMap<(u32, String), Duration>,
+}
+
+impl Baseline {
+    /// Parses lines of `<day> <stage> <median in ns>`. Blank lines and `#` comments are ignored.
+    pub fn parse(text: &str) -> Result<Baseline, String> {
+        let mut medians = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let [day, stage, nanos] = words[..] else {
+                return Err(format!("line {}: expected <day> <stage> <nanos>", i + 1));
+            };
+            let (Ok(day), Ok(nanos)) = (day.parse(), nanos.parse()) else {
+                return Err(format!("line {}: bad number", i + 1));
+            };
+            medians.insert((day, stage.to_string()), Duration::from_nanos(nanos));
+        }
+        Ok(Baseline { medians })
+    }
+
+    /// Loads a baseline, or an empty one if the file doesn't exist yet.
+    pub fn load(path: &Path) -> io::Result<Baseline> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Baseline::default()),
+            Err(err) => return Err(err),
+        };
+        Baseline::parse(&text).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {err}", path.display()),
+            )
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut text = String::new();
+        for ((day, stage), median) in &self.medians {
+            text += &format!("{day} {stage} {}\n", median.as_nanos());
+        }
+        fs::write(path, text)
+    }
+
+    pub fn get(&self, day: u32, stage: &str) -> Option<Duration> {
+        self.medians.get(&(day, stage.to_string())).copied()
+    }
+
+    pub fn set(&mut self, day: u32, stage: &str, median: Duration) {
+        self.medians.insert((day, stage.to_string()), median);
+    }
+
+    /// How `median` compares to the baseline, e.g. `+35%`, flagged if it's a regression. A
+    /// baseline under `NOISE_FLOOR` is compared in nanoseconds, and only counts as regressed once
+    /// `median` is well clear of the floor.
+    pub fn compare(&self, day: u32, stage: &str, median: Duration) -> Option<String> {
+        let old = self.get(day, stage)?;
+        let (change, regressed) = if old < NOISE_FLOOR {
+            let nanos = median.as_nanos() as i128 - old.as_nanos() as i128;
+            let floor = NOISE_FLOOR.as_secs_f64() * REGRESSION_THRESHOLD;
+            (
+                format!("{nanos:+}ns vs baseline"),
+                median.as_secs_f64() > floor,
+            )
+        } else {
+            let ratio = median.as_secs_f64() / old.as_secs_f64();
+            let change = format!("{:+.0}% vs baseline", (ratio - 1.0) * 100.0);
+            (change, ratio > REGRESSION_THRESHOLD)
+        };
+        if regressed {
+            Some(format!("{change}  REGRESSION"))
+        } else {
+            Some(change)
+        }
+    }
+}
+
+#[test]
+fn test_baseline() {
+    let mut baseline = Baseline::parse("1 parse 1000\n1 part1 2000000\n").unwrap();
+    assert_eq!(baseline.get(1, "part1"), Some(Duration::from_millis(2)));
+    assert_eq!(baseline.get(1, "part2"), None);
+    assert_eq!(
+        baseline.compare(1, "parse", Duration::from_nanos(1100)),
+        Some("+10% vs baseline".to_string())
+    );
+    assert_eq!(
+        baseline.compare(1, "parse", Duration::from_nanos(2000)),
+        Some("+100% vs baseline  REGRESSION".to_string())
+    );
+    baseline.set(1, "part2", Duration::from_micros(5));
+    assert_eq!(baseline.get(1, "part2"), Some(Duration::from_micros(5)));
+    assert!(Baseline::parse("1 parse").is_err());
+
+    let baseline = Baseline::parse("# saved by aoc bench\n\n1 parse 0\n1 part1 900\n\n").unwrap();
+    assert_eq!(baseline.get(1, "parse"), Some(Duration::ZERO));
+    assert_eq!(
+        baseline.compare(1, "parse", Duration::from_nanos(40)),
+        Some("+40ns vs baseline".to_string())
+    );
+    assert_eq!(
+        baseline.compare(1, "part1", Duration::from_nanos(850)),
+        Some("-50ns vs baseline".to_string())
+    );
+    assert_eq!(
+        baseline.compare(1, "parse", Duration::from_micros(5)),
+        Some("+5000ns vs baseline  REGRESSION".to_string())
+    );
+}
@@ -1,50 +1,43 @@
-use std::{
-    collections::HashMap,
-    io::{stdin, Read},
-    iter::zip,
-};
+use advent_of_code_2024::parse::{end_of, parse_at};
+use advent_of_code_2024::{Answer, ParseError, Solution};
+use std::{collections::HashMap, iter::zip};
 
-#[cfg(test)]
-static TEST_INPUT: &str = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
-
-fn parse(input: &str) -> (Vec<i32>, Vec<i32>) {
+fn parse(input: &str) -> Result<(Vec<i32>, Vec<i32>), ParseError> {
     let mut list1 = Vec::<i32>::new();
     let mut list2 = Vec::<i32>::new();
 
     for line in input.lines() {
-        let words: Vec<&str> = line.split_whitespace().collect();
-        let i1: i32 = words[0].parse().expect("not an integer");
-        let i2: i32 = words[1].parse().expect("not an integer");
+        let mut words = line.split_whitespace();
+        let i1: i32 = parse_at(input, words.next().unwrap_or(end_of(line)), "integer")?;
+        let i2: i32 = parse_at(input, words.next().unwrap_or(end_of(line)), "integer")?;
         list1.push(i1);
         list2.push(i2);
     }
 
-    (list1, list2)
+    Ok((list1, list2))
+}
+
+#[test]
+fn test_parse_error() {
+    let err = parse("3   4\n4   x\n").unwrap_err();
+    assert_eq!((err.line, err.column, err.found.as_str()), (2, 5, "x"));
+    let err = parse("3   4\n4\n").unwrap_err();
+    assert_eq!((err.line, err.column, err.found.as_str()), (2, 2, ""));
 }
 
-fn part1(input: &str) -> i32 {
-    let (mut list1, mut list2) = parse(input);
+fn part1((list1, list2): &(Vec<i32>, Vec<i32>)) -> i32 {
+    let mut list1 = list1.clone();
+    let mut list2 = list2.clone();
     list1.sort();
     list2.sort();
 
     zip(list1, list2).map(|(a, b)| (a - b).abs()).sum()
 }
 
-#[test]
-fn test_part1() {
-    assert_eq!(part1(TEST_INPUT), 11);
-}
-
-fn part2(input: &str) -> i32 {
-    let (list1, list2) = parse(input);
+fn part2((list1, list2): &(Vec<i32>, Vec<i32>)) -> i32 {
     let mut occurrences = HashMap::<i32, i32>::new();
 
-    for x in list2 {
+    for &x in list2 {
         occurrences.insert(x, occurrences.get(&x).unwrap_or(&0) + 1);
     }
 
@@ -54,14 +47,22 @@ fn part2(input: &str) -> i32 {
         .sum()
 }
 
-#[test]
-fn test_part2() {
-    assert_eq!(part2(TEST_INPUT), 31);
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+    type Input = (Vec<i32>, Vec<i32>);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
 }
 
 fn main() {
-    let mut input = String::new();
-    stdin().read_to_string(&mut input).unwrap();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    advent_of_code_2024::run::<Day01>();
 }
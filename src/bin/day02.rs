@@ -1,20 +1,13 @@
-use std::io::{stdin, Read};
+use advent_of_code_2024::parse::parse_at;
+use advent_of_code_2024::{Answer, ParseError, Solution};
 use std::iter::zip;
 
-#[cfg(test)]
-static TEST_INPUT: &str = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
-
-fn parse(input: &str) -> Vec<Vec<i32>> {
+fn parse(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
     input
         .lines()
         .map(|line| {
             line.split_whitespace()
-                .map(|word| word.parse::<i32>().expect("should be integer"))
+                .map(|word| parse_at(input, word, "integer"))
                 .collect()
         })
         .collect()
@@ -32,16 +25,10 @@ fn is_safe(report: &&Vec<i32>) -> bool {
         || diffs.clone().all(|item| (-3..=-1).contains(&item))
 }
 
-fn part1(input: &str) -> i32 {
-    let reports = parse(input);
+fn part1(reports: &[Vec<i32>]) -> i32 {
     reports.iter().filter(is_safe).count().try_into().unwrap()
 }
 
-#[test]
-fn test_part1() {
-    assert_eq!(part1(TEST_INPUT), 2);
-}
-
 fn is_safe2(report: &&Vec<i32>) -> bool {
     if report.len() < 2 {
         return false;
@@ -92,19 +79,26 @@ fn test_safe2() {
     assert!(is_safe2(&&vec![1, 4, 3, 4, 5]));
 }
 
-fn part2(input: &str) -> i32 {
-    let reports = parse(input);
+fn part2(reports: &[Vec<i32>]) -> i32 {
     reports.iter().filter(is_safe2).count().try_into().unwrap()
 }
 
-#[test]
-fn test_part2() {
-    assert_eq!(part2(TEST_INPUT), 4);
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+    type Input = Vec<Vec<i32>>;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
 }
 
 fn main() {
-    let mut input = String::new();
-    stdin().read_to_string(&mut input).unwrap();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    advent_of_code_2024::run::<Day02>();
 }
@@ -1,20 +1,15 @@
+use advent_of_code_2024::parse::parse_at;
+use advent_of_code_2024::{Answer, ParseError, Solution};
 use regex::RegexBuilder;
-use std::io::{stdin, Read};
-
-#[cfg(test)]
-static TEST_INPUT: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-#[cfg(test)]
-static TEST_INPUT2: &str =
-    "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
 
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     Do,
     Dont,
     Mul(i32, i32),
 }
 
-fn parse(input: &str) -> Vec<Instruction> {
+fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
     // let re = Regex::new(r"(?<op>do|don't|mul)\((?:(?<a>\d+),(?<b>\d+))?\)").unwrap();
     let re = RegexBuilder::new(
         r"
@@ -35,19 +30,18 @@ fn parse(input: &str) -> Vec<Instruction> {
             "do" => result.push(Instruction::Do),
             "don't" => result.push(Instruction::Dont),
             "mul" => {
-                let a = cap.name("a").unwrap().as_str().parse().unwrap();
-                let b = cap.name("b").unwrap().as_str().parse().unwrap();
+                let a = parse_at(input, cap.name("a").unwrap().as_str(), "integer")?;
+                let b = parse_at(input, cap.name("b").unwrap().as_str(), "integer")?;
                 result.push(Instruction::Mul(a, b));
             }
             _ => panic!("bad match"),
         }
     }
-    result
+    Ok(result)
 }
 
-fn part1(input: &str) -> i32 {
-    let data = dbg!(parse(input));
-    data.into_iter()
+fn part1(data: &[Instruction]) -> i32 {
+    data.iter()
         .filter_map(|instr| match instr {
             Instruction::Mul(a, b) => Some(a * b),
             _ => None,
@@ -55,13 +49,7 @@ fn part1(input: &str) -> i32 {
         .sum()
 }
 
-#[test]
-fn test_part1() {
-    assert_eq!(part1(TEST_INPUT), 161);
-}
-
-fn part2(input: &str) -> i32 {
-    let data = dbg!(parse(input));
+fn part2(data: &[Instruction]) -> i32 {
     let mut result = 0;
     let mut enabled = true;
     for instr in data {
@@ -78,14 +66,22 @@ fn part2(input: &str) -> i32 {
     result
 }
 
-#[test]
-fn test_part2() {
-    assert_eq!(part2(TEST_INPUT2), 48);
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+    type Input = Vec<Instruction>;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
 }
 
 fn main() {
-    let mut input = String::new();
-    stdin().read_to_string(&mut input).unwrap();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    advent_of_code_2024::run::<Day03>();
 }
@@ -1,62 +1,38 @@
-use std::{
-    cmp::Ordering,
-    io::{stdin, Read},
-};
+use advent_of_code_2024::parse::parse_at;
+use advent_of_code_2024::{Answer, ParseError, Solution};
+use std::cmp::Ordering;
 
 #[cfg(test)]
-static TEST_INPUT: &str = "47|53
-97|13
-97|61
-97|47
-75|29
-61|13
-75|53
-29|13
-97|29
-53|29
-61|53
-97|53
-61|29
-47|13
-75|47
-97|75
-47|61
-75|61
-47|29
-75|13
-53|13
-
-75,47,61,53,29
-97,61,53,29,13
-75,29,13
-75,97,47,61,53
-61,13,29
-97,13,75,29,47";
+static TEST_INPUT: &str = include_str!("../../examples/day05/example.txt");
 
 #[derive(Debug)]
-struct Rule {
+pub struct Rule {
     earlier: i32,
     later: i32,
 }
 
-fn parse(input: &str) -> (Vec<Rule>, Vec<Vec<i32>>) {
+fn parse(input: &str) -> Result<(Vec<Rule>, Vec<Vec<i32>>), ParseError> {
     let rules = input
         .lines()
         .filter(|line| line.contains("|"))
         .map(|line| {
-            let mut nums = line.splitn(2, "|").map(|word| word.parse::<i32>().unwrap());
-            Rule {
-                earlier: nums.next().unwrap(),
-                later: nums.next().unwrap(),
-            }
+            let (earlier, later) = line.split_once("|").unwrap();
+            Ok(Rule {
+                earlier: parse_at(input, earlier, "page number")?,
+                later: parse_at(input, later, "page number")?,
+            })
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
     let updates: Vec<Vec<i32>> = input
         .lines()
         .filter(|line| line.contains(","))
-        .map(|line| line.split(",").map(|word| word.parse().unwrap()).collect())
-        .collect();
-    (rules, updates)
+        .map(|line| {
+            line.split(",")
+                .map(|word| parse_at(input, word, "page number"))
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((rules, updates))
 }
 
 fn satisfies(update: &[i32], rules: &[Rule]) -> bool {
@@ -73,12 +49,11 @@ fn satisfies(update: &[i32], rules: &[Rule]) -> bool {
     true
 }
 
-fn part1(input: &str) -> i32 {
-    let (rules, updates) = parse(input);
+fn part1((rules, updates): &(Vec<Rule>, Vec<Vec<i32>>)) -> i32 {
     updates
         .iter()
         .filter_map(|update| {
-            if satisfies(update, &rules) {
+            if satisfies(update, rules) {
                 Some(update[update.len() / 2])
             } else {
                 None
@@ -87,11 +62,6 @@ fn part1(input: &str) -> i32 {
         .sum()
 }
 
-#[test]
-fn test_part1() {
-    assert_eq!(part1(TEST_INPUT), 143);
-}
-
 fn cmp(a: i32, b: i32, rules: &[Rule]) -> Option<Ordering> {
     for rule in rules {
         if rule.earlier == a && rule.later == b {
@@ -123,41 +93,48 @@ fn reorder(update: &[i32], rules: &[Rule]) -> Vec<i32> {
 
 #[test]
 fn test_reorder() {
-    let (rules, _) = parse(TEST_INPUT);
+    let (rules, _) = parse(TEST_INPUT).unwrap();
     assert_eq!(
-        reorder(&vec![75, 97, 47, 61, 53], &rules),
+        reorder(&[75, 97, 47, 61, 53], &rules),
         vec![97, 75, 47, 61, 53]
     );
-    assert_eq!(reorder(&vec![61, 13, 29], &rules), vec![61, 29, 13]);
+    assert_eq!(reorder(&[61, 13, 29], &rules), vec![61, 29, 13]);
     assert_eq!(
-        reorder(&vec![97, 13, 75, 29, 47], &rules),
+        reorder(&[97, 13, 75, 29, 47], &rules),
         vec![97, 75, 47, 29, 13]
     );
 }
 
-fn part2(input: &str) -> i32 {
-    let (rules, updates) = parse(input);
+fn part2((rules, updates): &(Vec<Rule>, Vec<Vec<i32>>)) -> i32 {
     updates
         .iter()
         .filter_map(|update| {
-            if satisfies(update, &rules) {
+            if satisfies(update, rules) {
                 None
             } else {
-                Some(reorder(update, &rules))
+                Some(reorder(update, rules))
             }
         })
         .map(|update| update[update.len() / 2])
         .sum()
 }
 
-#[test]
-fn test_part2() {
-    assert_eq!(part2(TEST_INPUT), 123);
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+    type Input = (Vec<Rule>, Vec<Vec<i32>>);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
 }
 
 fn main() {
-    let mut input = String::new();
-    stdin().read_to_string(&mut input).unwrap();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    advent_of_code_2024::run::<Day05>();
 }
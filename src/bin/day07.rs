@@ -1,29 +1,19 @@
-use std::io::{stdin, Read};
+use advent_of_code_2024::parse::{end_of, parse_at};
+use advent_of_code_2024::{Answer, ParseError, Solution};
 
-#[cfg(test)]
-static TEST_INPUT: &str = "190: 10 19
-3267: 81 40 27
-83: 17 5
-156: 15 6
-7290: 6 8 6 15
-161011: 16 10 13
-192: 17 8 14
-21037: 9 7 18 13
-292: 11 6 16 20";
-
-fn parse(input: &str) -> Vec<(i64, Vec<i64>)> {
+fn parse(input: &str) -> Result<Vec<(i64, Vec<i64>)>, ParseError> {
     input
         .lines()
         .map(|line| {
-            let mut line = line.splitn(2, ": ");
-            let expected: i64 = line.next().unwrap().parse().unwrap();
-            let nums: Vec<i64> = line
-                .next()
-                .unwrap()
+            let Some((expected, nums)) = line.split_once(": ") else {
+                return Err(ParseError::at(input, end_of(line), "\": \""));
+            };
+            let expected: i64 = parse_at(input, expected, "integer")?;
+            let nums: Vec<i64> = nums
                 .split(" ")
-                .map(|x| x.parse().unwrap())
-                .collect();
-            (expected, nums)
+                .map(|x| parse_at(input, x, "integer"))
+                .collect::<Result<_, _>>()?;
+            Ok((expected, nums))
         })
         .collect()
 }
@@ -50,8 +40,7 @@ fn is_satisfiable(expected: i64, nums: &[i64]) -> bool {
     false
 }
 
-fn part1(input: &str) -> i64 {
-    let equations = parse(input);
+fn part1(equations: &[(i64, Vec<i64>)]) -> i64 {
     equations
         .iter()
         .filter(|(expected, nums)| is_satisfiable(*expected, nums))
@@ -59,11 +48,6 @@ fn part1(input: &str) -> i64 {
         .sum()
 }
 
-#[test]
-fn test_part1() {
-    assert_eq!(part1(TEST_INPUT), 3749);
-}
-
 fn is_satisfiable2(expected: i64, nums: &[i64]) -> bool {
     // iterate all possible op values - 0 is +, 1 is *, 2 is concat
     // is there a faster way than brute force?
@@ -97,13 +81,12 @@ fn is_satisfiable2(expected: i64, nums: &[i64]) -> bool {
 
 #[test]
 fn test_is_satisfiable2() {
-    assert!(is_satisfiable2(156, &vec![15, 6]));
-    assert!(is_satisfiable2(7290, &vec![6, 8, 6, 15]));
-    assert!(is_satisfiable2(192, &vec![17, 8, 14]));
+    assert!(is_satisfiable2(156, &[15, 6]));
+    assert!(is_satisfiable2(7290, &[6, 8, 6, 15]));
+    assert!(is_satisfiable2(192, &[17, 8, 14]));
 }
 
-fn part2(input: &str) -> i64 {
-    let equations = parse(input);
+fn part2(equations: &[(i64, Vec<i64>)]) -> i64 {
     equations
         .iter()
         .filter(|(expected, nums)| is_satisfiable2(*expected, nums))
@@ -111,14 +94,22 @@ fn part2(input: &str) -> i64 {
         .sum()
 }
 
-#[test]
-fn test_part2() {
-    assert_eq!(part2(TEST_INPUT), 11387);
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+    type Input = Vec<(i64, Vec<i64>)>;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
 }
 
 fn main() {
-    let mut input = String::new();
-    stdin().read_to_string(&mut input).unwrap();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    advent_of_code_2024::run::<Day07>();
 }
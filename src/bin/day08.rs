@@ -1,39 +1,20 @@
-use advent_of_code_2024::{Grid, Vec2};
-use std::{
-    collections::HashSet,
-    io::{stdin, Read},
-};
+use advent_of_code_2024::{Answer, Grid, ParseError, Solution, Vec2};
+use std::collections::HashSet;
 
-#[cfg(test)]
-static TEST_INPUT: &str = "............
-........0...
-.....0......
-.......0....
-....0.......
-......A.....
-............
-............
-........A...
-.........A..
-............
-............";
-
-fn parse(input: &str) -> Grid {
-    Grid {
-        data: input.lines().map(|line| line.chars().collect()).collect(),
-    }
+fn parse(input: &str) -> Result<Grid, ParseError> {
+    Grid::try_from_str(input).map_err(|err| err.at(input))
 }
 
-fn get_frequencies(grid: &Grid) -> HashSet<char> {
-    let mut unique_chars: HashSet<char> = HashSet::new();
+fn get_frequencies(grid: &Grid) -> HashSet<u8> {
+    let mut unique_chars: HashSet<u8> = HashSet::new();
     for (_, c) in grid.iter_positions() {
         unique_chars.insert(c);
     }
-    unique_chars.remove(&'.');
+    unique_chars.remove(&b'.');
     unique_chars
 }
 
-fn get_antenna_positions(grid: &Grid, frequency: char) -> Vec<(usize, usize)> {
+fn get_antenna_positions(grid: &Grid, frequency: u8) -> Vec<(usize, usize)> {
     let mut antennas: Vec<(usize, usize)> = vec![];
     for ((x, y), c) in grid.iter_positions() {
         if c == frequency {
@@ -43,32 +24,24 @@ fn get_antenna_positions(grid: &Grid, frequency: char) -> Vec<(usize, usize)> {
     antennas
 }
 
-fn part1(input: &str) -> usize {
-    let grid = parse(input);
+fn part1(grid: &Grid) -> usize {
     let mut antinodes: HashSet<(usize, usize)> = HashSet::new();
-    for freq in get_frequencies(&grid) {
+    for freq in get_frequencies(grid) {
         // println!("{freq}: {antennas:?}");
-        let antennas = get_antenna_positions(&grid, freq);
+        let antennas = get_antenna_positions(grid, freq);
         for i in 0..antennas.len() - 1 {
             for j in i + 1..antennas.len() {
-                let a: Vec2<isize> = Vec2 {
-                    x: antennas[i].0 as isize,
-                    y: antennas[i].1 as isize,
-                };
-                let b: Vec2<isize> = Vec2 {
-                    x: antennas[j].0 as isize,
-                    y: antennas[j].1 as isize,
-                };
+                let a = Vec2::try_from(antennas[i]).unwrap();
+                let b = Vec2::try_from(antennas[j]).unwrap();
                 let delta = a - b;
                 // println!("{a:?} - {b:?} = {delta:?}");
                 let anti1 = a + delta;
                 let anti2 = b - delta;
                 // println!("{anti1:?} {anti2:?}");
-                if grid.rect().contains(anti1) {
-                    antinodes.insert((anti1.x as usize, anti1.y as usize));
-                }
-                if grid.rect().contains(anti2) {
-                    antinodes.insert((anti2.x as usize, anti2.y as usize));
+                for anti in [anti1, anti2] {
+                    if grid.rect().contains(anti) {
+                        antinodes.insert(anti.try_into().unwrap());
+                    }
                 }
             }
         }
@@ -77,43 +50,37 @@ fn part1(input: &str) -> usize {
     antinodes.len()
 }
 
-#[test]
-fn test_part1() {
-    assert_eq!(part1(TEST_INPUT), 14);
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
 }
 
-fn part2(input: &str) -> usize {
-    // I think this might be incomplete - distance between two antennas may not be a simplified
-    // ratio, e.g. (2,4) so we'd miss grid points at (1,2) intervals that are also collinear.
-    // It worked for my input though.
-    let grid = parse(input);
+fn part2(grid: &Grid) -> usize {
     let mut antinodes: HashSet<(usize, usize)> = HashSet::new();
-    for freq in get_frequencies(&grid) {
+    for freq in get_frequencies(grid) {
         // println!("{freq}: {antennas:?}");
-        let antennas = get_antenna_positions(&grid, freq);
+        let antennas = get_antenna_positions(grid, freq);
         for i in 0..antennas.len() - 1 {
-            antinodes.insert(antennas[i]);
             for j in i + 1..antennas.len() {
-                let a: Vec2<isize> = Vec2 {
-                    x: antennas[i].0 as isize,
-                    y: antennas[i].1 as isize,
-                };
-                let b: Vec2<isize> = Vec2 {
-                    x: antennas[j].0 as isize,
-                    y: antennas[j].1 as isize,
-                };
-                antinodes.insert(antennas[j]);
+                let a = Vec2::try_from(antennas[i]).unwrap();
+                let b = Vec2::try_from(antennas[j]).unwrap();
                 let delta = a - b;
+                // reduce the step so we also find points between the antennas, e.g. (1,2) steps
+                // when they're (2,4) apart
+                let step = delta / gcd(delta.x.unsigned_abs(), delta.y.unsigned_abs()) as isize;
                 // println!("{a:?} - {b:?} = {delta:?}");
-                let mut anti = a + delta;
+                let mut anti = a;
                 while grid.rect().contains(anti) {
-                    antinodes.insert((anti.x as usize, anti.y as usize));
-                    anti += delta;
+                    antinodes.insert(anti.try_into().unwrap());
+                    anti += step;
                 }
-                let mut anti = b - delta;
+                let mut anti = a - step;
                 while grid.rect().contains(anti) {
-                    antinodes.insert((anti.x as usize, anti.y as usize));
-                    anti -= delta;
+                    antinodes.insert(anti.try_into().unwrap());
+                    anti -= step;
                 }
             }
         }
@@ -122,14 +89,22 @@ fn part2(input: &str) -> usize {
     antinodes.len()
 }
 
-#[test]
-fn test_part2() {
-    assert_eq!(part2(TEST_INPUT), 34);
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u32 = 8;
+    type Input = Grid;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
 }
 
 fn main() {
-    let mut input = String::new();
-    stdin().read_to_string(&mut input).unwrap();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    advent_of_code_2024::run::<Day08>();
 }
@@ -1,13 +1,17 @@
-use std::io::{stdin, Read};
+use advent_of_code_2024::{debug, Answer, ParseError, Solution};
 
-#[cfg(test)]
-static TEST_INPUT: &str = "2333133121414131402";
-
-fn parse(input: &str) -> Vec<usize> {
-    input
-        .trim()
-        .chars()
-        .map(|c| c.to_string().parse().unwrap())
+fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    let disk_map = input.trim();
+    disk_map
+        .char_indices()
+        .map(|(i, c)| match c.to_digit(10) {
+            Some(digit) => Ok(digit as usize),
+            None => Err(ParseError::at(
+                input,
+                &disk_map[i..i + c.len_utf8()],
+                "digit",
+            )),
+        })
         .collect()
 }
 
@@ -26,7 +30,7 @@ fn map_to_disk(disk_map: &[usize]) -> Vec<Option<usize>> {
 #[test]
 fn test_map_to_disk() {
     assert_eq!(
-        map_to_disk(&parse("12345")),
+        map_to_disk(&parse("12345").unwrap()),
         vec![
             Some(0),
             None,
@@ -47,11 +51,10 @@ fn test_map_to_disk() {
     );
 }
 
-fn part1(input: &str) -> usize {
-    let disk_map = parse(input);
-    println!("{}", disk_map.len());
-    let mut disk = map_to_disk(&disk_map);
-    println!("{}", disk.len());
+fn part1(disk_map: &[usize]) -> usize {
+    debug!("disk map: {} entries", disk_map.len());
+    let mut disk = map_to_disk(disk_map);
+    debug!("disk: {} blocks", disk.len());
     let mut i = 0;
     while i < disk.len() {
         if disk[i].is_some() {
@@ -66,44 +69,35 @@ fn part1(input: &str) -> usize {
         }
         i += 1;
     }
-    println!("{}", disk.len());
+    debug!("compacted disk: {} blocks", disk.len());
     disk.iter()
         .enumerate()
         .map(|(i, block)| i * block.unwrap())
         .sum()
 }
 
-#[test]
-fn test_part1() {
-    assert_eq!(part1(TEST_INPUT), 1928);
-}
-
 #[derive(Debug, Clone, Copy)]
 enum Extent {
     File { id: usize, size: usize },
     Free(usize),
 }
 
-fn parse2(input: &str) -> Vec<Extent> {
-    input
-        .trim()
-        .chars()
+fn to_extents(disk_map: &[usize]) -> Vec<Extent> {
+    disk_map
+        .iter()
         .enumerate()
-        .map(|(i, c)| {
+        .map(|(i, &size)| {
             if i % 2 == 0 {
-                Extent::File {
-                    id: i / 2,
-                    size: c.to_digit(10).unwrap() as usize,
-                }
+                Extent::File { id: i / 2, size }
             } else {
-                Extent::Free(c.to_digit(10).unwrap() as usize)
+                Extent::Free(size)
             }
         })
         .collect()
 }
 
-fn part2(input: &str) -> usize {
-    let mut disk = parse2(input);
+fn part2(disk_map: &[usize]) -> usize {
+    let mut disk = to_extents(disk_map);
     let mut cur_pos = disk.len() - 1;
     let Extent::File {
         id: mut cur_id,
@@ -160,14 +154,22 @@ fn part2(input: &str) -> usize {
     result
 }
 
-#[test]
-fn test_part2() {
-    assert_eq!(part2(TEST_INPUT), 2858);
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u32 = 9;
+    type Input = Vec<usize>;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
 }
 
 fn main() {
-    let mut input = String::new();
-    stdin().read_to_string(&mut input).unwrap();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    advent_of_code_2024::run::<Day09>();
 }
@@ -1,40 +1,29 @@
-use advent_of_code_2024::{Direction, Grid, Vec2};
-use std::{
-    collections::{HashSet, VecDeque},
-    io::{stdin, Read},
-};
+use advent_of_code_2024::{Answer, Grid, ParseError, Solution, Vec2};
+use std::collections::{HashSet, VecDeque};
 
-#[cfg(test)]
-static TEST_INPUT: &str = "89010123
-78121874
-87430965
-96549874
-45678903
-32019012
-01329801
-10456732";
-
-fn parse(input: &str) -> Grid {
-    Grid::from(input)
+fn parse(input: &str) -> Result<Grid<u32>, ParseError> {
+    if let Some((i, c)) = input
+        .char_indices()
+        .find(|&(_, c)| !c.is_ascii_digit() && c != '\n')
+    {
+        let found = &input[i..i + c.len_utf8()];
+        return Err(ParseError::at(input, found, "height digit"));
+    }
+    Grid::try_parse_with(input, |c| c.to_digit(10).unwrap()).map_err(|err| err.at(input))
 }
 
-fn trailhead_score(grid: &Grid, trailhead: &Vec2<isize>) -> usize {
+fn trailhead_score(grid: &Grid<u32>, trailhead: &Vec2<isize>) -> usize {
     let mut q: VecDeque<Vec2<isize>> = VecDeque::from([*trailhead]);
     let mut reachable: HashSet<Vec2<isize>> = HashSet::new();
     while !q.is_empty() {
         let pos = q.pop_front().unwrap();
-        let c = grid.grid_get(pos.x, pos.y).unwrap();
-        if c == '9' {
+        let height = grid[pos];
+        if height == 9 {
             reachable.insert(pos);
             continue;
         }
-        let next_c = c
-            .to_digit(10)
-            .map(|x| char::from_digit(x + 1, 10).unwrap())
-            .unwrap();
-        for dir in Direction::DIRECTIONS {
-            let next_pos = pos + dir.as_vec2();
-            if grid.grid_get(next_pos.x, next_pos.y) == Some(next_c) {
+        for (next_pos, &next) in grid.neighbours4(pos) {
+            if next == height + 1 {
                 q.push_back(next_pos);
             }
         }
@@ -42,43 +31,23 @@ fn trailhead_score(grid: &Grid, trailhead: &Vec2<isize>) -> usize {
     reachable.len()
 }
 
-fn part1(input: &str) -> usize {
-    let grid = parse(input);
-    let trailheads: Vec<Vec2<isize>> = grid
-        .iter_positions()
-        .filter_map(|((x, y), c)| match c {
-            '0' => Some(Vec2 {
-                x: x as isize,
-                y: y as isize,
-            }),
-            _ => None,
-        })
-        .collect();
-    trailheads.iter().map(|t| trailhead_score(&grid, t)).sum()
-}
-
-#[test]
-fn test_part1() {
-    assert_eq!(part1(TEST_INPUT), 36);
+fn part1(grid: &Grid<u32>) -> usize {
+    let trailheads = grid.positions_of(&0);
+    trailheads.iter().map(|t| trailhead_score(grid, t)).sum()
 }
 
-fn trailhead_rating(grid: &Grid, trailhead: &Vec2<isize>) -> usize {
+fn trailhead_rating(grid: &Grid<u32>, trailhead: &Vec2<isize>) -> usize {
     let mut q: VecDeque<Vec2<isize>> = VecDeque::from([*trailhead]);
     let mut rating = 0;
     while !q.is_empty() {
         let pos = q.pop_front().unwrap();
-        let c = grid.grid_get(pos.x, pos.y).unwrap();
-        if c == '9' {
+        let height = grid[pos];
+        if height == 9 {
             rating += 1;
             continue;
         }
-        let next_c = c
-            .to_digit(10)
-            .map(|x| char::from_digit(x + 1, 10).unwrap())
-            .unwrap();
-        for dir in Direction::DIRECTIONS {
-            let next_pos = pos + dir.as_vec2();
-            if grid.grid_get(next_pos.x, next_pos.y) == Some(next_c) {
+        for (next_pos, &next) in grid.neighbours4(pos) {
+            if next == height + 1 {
                 q.push_back(next_pos);
             }
         }
@@ -86,29 +55,27 @@ fn trailhead_rating(grid: &Grid, trailhead: &Vec2<isize>) -> usize {
     rating
 }
 
-fn part2(input: &str) -> usize {
-    let grid = parse(input);
-    let trailheads: Vec<Vec2<isize>> = grid
-        .iter_positions()
-        .filter_map(|((x, y), c)| match c {
-            '0' => Some(Vec2 {
-                x: x as isize,
-                y: y as isize,
-            }),
-            _ => None,
-        })
-        .collect();
-    trailheads.iter().map(|t| trailhead_rating(&grid, t)).sum()
+fn part2(grid: &Grid<u32>) -> usize {
+    let trailheads = grid.positions_of(&0);
+    trailheads.iter().map(|t| trailhead_rating(grid, t)).sum()
 }
 
-#[test]
-fn test_part2() {
-    assert_eq!(part2(TEST_INPUT), 81);
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    type Input = Grid<u32>;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
 }
 
 fn main() {
-    let mut input = String::new();
-    stdin().read_to_string(&mut input).unwrap();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    advent_of_code_2024::run::<Day10>();
 }
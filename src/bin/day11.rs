@@ -1,9 +1,10 @@
-use std::io::{stdin, Read};
+use advent_of_code_2024::parse::parse_at;
+use advent_of_code_2024::{debug, Answer, ParseError, Solution};
 
-fn parse(input: &str) -> Vec<usize> {
+fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
     input
         .split_whitespace()
-        .map(|x| x.parse().unwrap())
+        .map(|x| parse_at(input, x, "integer"))
         .collect()
 }
 
@@ -14,7 +15,7 @@ fn step(stones: &mut Vec<usize>) {
         if stone == "0" {
             stones[i] = 1;
             i += 1;
-        } else if stone.len() % 2 == 0 {
+        } else if stone.len().is_multiple_of(2) {
             // let st1 = &stone[0..stone.len() / 2];
             // let st2 = &stone[stone.len() / 2..stone.len()];
             // stones[i] = st1.parse().unwrap();
@@ -35,31 +36,25 @@ fn step(stones: &mut Vec<usize>) {
 
 #[test]
 fn test_step() {
-    let mut stones = parse("0 1 10 99 999");
+    let mut stones = parse("0 1 10 99 999").unwrap();
     step(&mut stones);
     assert_eq!(stones, vec![1, 2024, 1, 0, 9, 9, 2021976]);
 }
 
-fn part1(input: &str) -> usize {
-    let mut stones = parse(input);
+fn part1(stones: &[usize]) -> usize {
+    let mut stones = stones.to_vec();
     for _ in 0..25 {
         step(&mut stones);
     }
     stones.len()
 }
 
-#[test]
-fn test_part1() {
-    assert_eq!(part1("125 17"), 55312);
-}
-
-fn part2(input: &str) -> usize {
-    let stones = parse(input);
+fn part2(stones: &[usize]) -> usize {
     let mut result = 0;
-    for stone in stones {
+    for &stone in stones {
         let mut stones = vec![stone];
         for i in 0..75 {
-            println!("{i}");
+            debug!("stone {stone}: blink {i}, {} stones", stones.len());
             step(&mut stones);
         }
         result += stones.len();
@@ -67,15 +62,22 @@ fn part2(input: &str) -> usize {
     result
 }
 
-#[test]
-#[ignore]
-fn test_part2() {
-    assert_eq!(part2(""), 0);
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    type Input = Vec<usize>;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
 }
 
 fn main() {
-    let mut input = String::new();
-    stdin().read_to_string(&mut input).unwrap();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    advent_of_code_2024::run::<Day11>();
 }
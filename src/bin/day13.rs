@@ -1,82 +1,59 @@
-use advent_of_code_2024::Vec2;
-use std::io::{stdin, Read};
+use advent_of_code_2024::parse::{end_of, parse_at};
+use advent_of_code_2024::{Answer, ParseError, Solution, Vec2};
 
-#[cfg(test)]
-static TEST_INPUT: &str = "Button A: X+94, Y+34
-Button B: X+22, Y+67
-Prize: X=8400, Y=5400
-
-Button A: X+26, Y+66
-Button B: X+67, Y+21
-Prize: X=12748, Y=12176
-
-Button A: X+17, Y+86
-Button B: X+84, Y+37
-Prize: X=7870, Y=6450
-
-Button A: X+69, Y+23
-Button B: X+27, Y+71
-Prize: X=18641, Y=10279";
-
-#[derive(Debug)]
-struct Cost {
+#[derive(Debug, Clone)]
+pub struct Cost {
     a: usize,
     b: usize,
 }
 
-#[derive(Debug)]
-struct ClawMachine {
+#[derive(Debug, Clone)]
+pub struct ClawMachine {
     a: Vec2<isize>,
     b: Vec2<isize>,
     prize: Vec2<isize>,
     cost: Cost,
 }
 
-fn parse(input: &str) -> Vec<ClawMachine> {
-    fn next_number(input: &mut &str) -> Option<usize> {
-        while input.chars().next().is_some_and(|c| !c.is_ascii_digit()) {
-            *input = &input[1..];
-        }
-        let mut end = 0;
-        while end < input.len() {
-            if input[end..end + 1]
-                .chars()
-                .next()
-                .is_some_and(|c| c.is_ascii_digit())
-            {
-                end += 1;
-            } else {
-                break;
-            }
+fn parse(input: &str) -> Result<Vec<ClawMachine>, ParseError> {
+    fn next_number<'a>(input: &mut &'a str) -> Option<&'a str> {
+        while let Some(c) = input.chars().next().filter(|c| !c.is_ascii_digit()) {
+            *input = &input[c.len_utf8()..];
         }
+        let end = input
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(input.len());
         if end > 0 {
             let token = &input[0..end];
             *input = &input[end..];
-            token.parse().ok()
+            Some(token)
         } else {
             None
         }
     }
+    let number = |ptr: &mut &str| -> Result<isize, ParseError> {
+        parse_at(input, next_number(ptr).unwrap_or(end_of(input)), "number")
+    };
     let mut ptr: &str = input;
     let mut result = Vec::new();
     while let Some(ax) = next_number(&mut ptr) {
         result.push(ClawMachine {
             a: Vec2 {
-                x: ax as isize,
-                y: next_number(&mut ptr).unwrap() as isize,
+                x: parse_at(input, ax, "number")?,
+                y: number(&mut ptr)?,
             },
             b: Vec2 {
-                x: next_number(&mut ptr).unwrap() as isize,
-                y: next_number(&mut ptr).unwrap() as isize,
+                x: number(&mut ptr)?,
+                y: number(&mut ptr)?,
             },
             prize: Vec2 {
-                x: next_number(&mut ptr).unwrap() as isize,
-                y: next_number(&mut ptr).unwrap() as isize,
+                x: number(&mut ptr)?,
+                y: number(&mut ptr)?,
             },
             cost: Cost { a: 3, b: 1 },
         })
     }
-    result
+    Ok(result)
 }
 
 fn solve_machine(machine: &ClawMachine) -> Option<usize> {
@@ -103,16 +80,10 @@ fn solve_machine(machine: &ClawMachine) -> Option<usize> {
     sol
 }
 
-fn part1(input: &str) -> usize {
-    let machines = parse(input);
+fn part1(machines: &[ClawMachine]) -> usize {
     machines.iter().filter_map(solve_machine).sum()
 }
 
-#[test]
-fn test_part1() {
-    assert_eq!(part1(TEST_INPUT), 480);
-}
-
 fn solve_machine2(machine: &ClawMachine) -> Option<usize> {
     // solve as a system of equations
     // ax*a + bx * b = px
@@ -152,8 +123,8 @@ fn test_solve2() {
             a: Vec2 { x: 94, y: 34 },
             b: Vec2 { x: 22, y: 67 },
             prize: Vec2 {
-                x: 1_000_000_000_8400,
-                y: 1_000_000_000_5400
+                x: 10_000_000_008_400,
+                y: 10_000_000_005_400
             },
             cost: Cost { a: 3, b: 1 }
         }),
@@ -163,8 +134,8 @@ fn test_solve2() {
         a: Vec2 { x: 26, y: 66 },
         b: Vec2 { x: 67, y: 21 },
         prize: Vec2 {
-            x: 1_000_000_001_2748,
-            y: 1_000_000_001_2176
+            x: 10_000_000_012_748,
+            y: 10_000_000_012_176
         },
         cost: Cost { a: 3, b: 1 }
     })
@@ -174,8 +145,8 @@ fn test_solve2() {
             a: Vec2 { x: 17, y: 86 },
             b: Vec2 { x: 84, y: 37 },
             prize: Vec2 {
-                x: 1_000_000_000_7870,
-                y: 1_000_000_000_6450
+                x: 10_000_000_007_870,
+                y: 10_000_000_006_450
             },
             cost: Cost { a: 3, b: 1 }
         }),
@@ -185,16 +156,16 @@ fn test_solve2() {
         a: Vec2 { x: 69, y: 23 },
         b: Vec2 { x: 27, y: 71 },
         prize: Vec2 {
-            x: 1_000_000_001_8641,
-            y: 1_000_000_001_0279
+            x: 10_000_000_018_641,
+            y: 10_000_000_010_279
         },
         cost: Cost { a: 3, b: 1 }
     })
     .is_some());
 }
 
-fn part2(input: &str) -> usize {
-    let mut machines = parse(input);
+fn part2(machines: &[ClawMachine]) -> usize {
+    let mut machines = machines.to_vec();
     for machine in &mut machines {
         machine.prize.x += 10_000_000_000_000;
         machine.prize.y += 10_000_000_000_000;
@@ -202,15 +173,22 @@ fn part2(input: &str) -> usize {
     machines.iter().filter_map(solve_machine2).sum()
 }
 
-#[test]
-#[ignore]
-fn test_part2() {
-    assert_eq!(part2(TEST_INPUT), 0);
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    type Input = Vec<ClawMachine>;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
 }
 
 fn main() {
-    let mut input = String::new();
-    stdin().read_to_string(&mut input).unwrap();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    advent_of_code_2024::run::<Day13>();
 }
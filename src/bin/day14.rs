@@ -1,141 +1,106 @@
-use advent_of_code_2024::{Grid, Vec2};
-use std::io::{stdin, Read};
+use advent_of_code_2024::log::{self, Level};
+use advent_of_code_2024::parse::{end_of, parse_at};
+use advent_of_code_2024::{debug, Answer, Grid, ParseError, Solution, Torus, Vec2};
 
 #[cfg(test)]
-static TEST_INPUT: &str = "p=0,4 v=3,-3
-p=6,3 v=-1,-3
-p=10,3 v=-1,2
-p=2,0 v=2,-1
-p=0,0 v=1,3
-p=3,0 v=-2,-2
-p=7,6 v=-1,-3
-p=3,0 v=-1,-2
-p=9,3 v=2,3
-p=7,3 v=-1,2
-p=2,4 v=2,-3
-p=9,5 v=-3,-3";
+static TEST_INPUT: &str = include_str!("../../examples/day14/example.txt");
 
-#[derive(Debug)]
-struct Robot {
+#[derive(Debug, Clone)]
+pub struct Robot {
     p: Vec2<isize>,
     v: Vec2<isize>,
 }
 
-fn parse(input: &str) -> Vec<Robot> {
+fn parse(input: &str) -> Result<Vec<Robot>, ParseError> {
+    // each field looks like `p=0,4`
+    let vec2 = |field: &str, name: &str| -> Result<Vec2<isize>, ParseError> {
+        let Some(xy) = field.strip_prefix(name).and_then(|f| f.strip_prefix("=")) else {
+            return Err(ParseError::at(input, field, format!("\"{name}=\"")));
+        };
+        let Some((x, y)) = xy.split_once(",") else {
+            return Err(ParseError::at(input, end_of(xy), "\",\""));
+        };
+        Ok(Vec2 {
+            x: parse_at(input, x, "integer")?,
+            y: parse_at(input, y, "integer")?,
+        })
+    };
     input
         .lines()
         .map(|line| {
-            let (p_str, v_str) = line.trim().split_once(" ").unwrap();
-            let (_, p_str) = p_str.split_once("=").unwrap();
-            let (px, py) = p_str.split_once(",").unwrap();
-            let (_, v_str) = v_str.split_once("=").unwrap();
-            let (vx, vy) = v_str.split_once(",").unwrap();
-            Robot {
-                p: Vec2 {
-                    x: px.parse().unwrap(),
-                    y: py.parse().unwrap(),
-                },
-                v: Vec2 {
-                    x: vx.parse().unwrap(),
-                    y: vy.parse().unwrap(),
-                },
-            }
+            let line = line.trim();
+            let Some((p_str, v_str)) = line.split_once(" ") else {
+                return Err(ParseError::at(input, end_of(line), "velocity"));
+            };
+            Ok(Robot {
+                p: vec2(p_str, "p")?,
+                v: vec2(v_str, "v")?,
+            })
         })
         .collect()
 }
 
-fn part1(input: &str, w: isize, h: isize) -> isize {
-    let mut robots = parse(input);
-    for robot in &mut robots {
-        let mut x = (robot.p.x + robot.v.x * 100) % w;
-        if x < 0 {
-            x += w;
-        }
-        let mut y = (robot.p.y + robot.v.y * 100) % h;
-        if y < 0 {
-            y += h;
-        }
-        robot.p.x = x;
-        robot.p.y = y;
-    }
-    let q1: isize = robots
-        .iter()
-        .map(|robot| {
-            if robot.p.x < w / 2 && robot.p.y < h / 2 {
-                1
-            } else {
-                0
-            }
-        })
-        .sum();
-    let q2: isize = robots
+fn part1(robots: &[Robot], board: Torus) -> usize {
+    board
+        .quadrant_counts(robots.iter().map(|robot| robot.p + robot.v * 100))
         .iter()
-        .map(|robot| {
-            if robot.p.x > w / 2 && robot.p.y < h / 2 {
-                1
-            } else {
-                0
-            }
-        })
-        .sum();
-    let q3: isize = robots
-        .iter()
-        .map(|robot| {
-            if robot.p.x < w / 2 && robot.p.y > h / 2 {
-                1
-            } else {
-                0
-            }
-        })
-        .sum();
-    let q4: isize = robots
-        .iter()
-        .map(|robot| {
-            if robot.p.x > w / 2 && robot.p.y > h / 2 {
-                1
-            } else {
-                0
-            }
-        })
-        .sum();
-    q1 * q2 * q3 * q4
-}
-
-#[test]
-fn test_part1() {
-    assert_eq!(part1(TEST_INPUT, 11, 7), 12);
+        .product()
 }
 
-fn part2(input: &str, w: isize, h: isize) {
-    let mut robots = parse(input);
+fn part2(robots: &[Robot], board: Torus) {
+    let mut robots = robots.to_vec();
     // let mut t = 0;
     let show_grid = |robots: &Vec<Robot>| {
-        let mut grid = Grid::of('.', w as usize, h as usize);
+        // building the grid is most of the work, so skip it unless it'll be shown
+        if !log::enabled(Level::Debug) {
+            return;
+        }
+        let mut grid = Grid::of(b'.', board.width(), board.height());
         for robot in robots {
-            grid.data[robot.p.y as usize][robot.p.x as usize] = 'X';
+            grid[robot.p] = b'X';
         }
-        println!("{grid}");
+        debug!("{grid}");
     };
     for t in 0..10000 {
         show_grid(&robots);
-        println!("{t}");
+        debug!("{t}");
         // t += 1;
         for robot in &mut robots {
-            robot.p.x = (robot.p.x + robot.v.x) % w;
-            robot.p.y = (robot.p.y + robot.v.y) % h;
-            if robot.p.x < 0 {
-                robot.p.x += w;
-            }
-            if robot.p.y < 0 {
-                robot.p.y += h;
-            }
+            robot.p = board.wrap(robot.p + robot.v);
         }
     }
 }
 
+/// The board the real input's robots move on.
+const BOARD: Torus = Torus::new(101, 103);
+
+/// The example's robots move on a smaller board than the real input's, which the runner can't
+/// know about, so part 1's example answer is checked here rather than in the example's answers.
+#[cfg(test)]
+const EXAMPLE_BOARD: Torus = Torus::new(11, 7);
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(&parse(TEST_INPUT).unwrap(), EXAMPLE_BOARD), 12);
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    type Input = Vec<Robot>;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input, BOARD).into()
+    }
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input, BOARD);
+        Answer::None
+    }
+}
+
 fn main() {
-    let mut input = String::new();
-    stdin().read_to_string(&mut input).unwrap();
-    println!("Part 1: {}", part1(&input, 101, 103));
-    part2(&input, 101, 103);
+    advent_of_code_2024::run::<Day14>();
 }
@@ -1,48 +1,45 @@
-use advent_of_code_2024::{Direction, Grid, Vec2};
-use std::io::{stdin, Read};
+use advent_of_code_2024::parse::end_of;
+use advent_of_code_2024::{
+    debug, trace, Answer, Color, Direction, Grid, Overlay, ParseError, Render, Solution, Vec2,
+};
+use std::io;
 
-#[cfg(test)]
-static TEST_INPUT_SMALL: &str = include_str!("test15_small.txt");
-#[cfg(test)]
-static TEST_INPUT_LARGE: &str = include_str!("test15_large.txt");
-
-fn parse(input: &str) -> (Grid, Vec<Direction>) {
-    let (grid, moves) = input.split_once("\n\n").unwrap();
+fn parse(input: &str) -> Result<(Grid, Vec<Direction>), ParseError> {
+    let Some((grid, moves)) = input.split_once("\n\n") else {
+        return Err(ParseError::at(
+            input,
+            end_of(input),
+            "blank line before moves",
+        ));
+    };
     let moves = moves
-        .chars()
-        .filter_map(|c| match c {
-            '^' => Some(Direction::Up),
-            '>' => Some(Direction::Right),
-            'v' => Some(Direction::Down),
-            '<' => Some(Direction::Left),
-            c => {
-                if c.is_whitespace() {
-                    None
-                } else {
-                    panic!("expected valid move")
-                }
-            }
+        .char_indices()
+        .filter(|(_, c)| !c.is_whitespace())
+        .map(|(i, c)| {
+            Direction::try_from(c).map_err(|c| {
+                let found = &moves[i..i + c.len_utf8()];
+                ParseError::at(input, found, "valid move")
+            })
         })
-        .collect();
-    (Grid::from(grid), moves)
+        .collect::<Result<_, _>>()?;
+    let grid = Grid::try_from_str(grid).map_err(|err| err.at(input))?;
+    Ok((grid, moves))
 }
 
 fn do_move(grid: &mut Grid, pos: Vec2<isize>, direction: Direction) -> bool {
     let next_pos = pos + direction.as_vec2();
     match grid.grid_get(next_pos.x, next_pos.y) {
         None => false,
-        Some('#') => false,
-        Some('.') => {
-            grid.data[next_pos.y as usize][next_pos.x as usize] =
-                grid.data[pos.y as usize][pos.x as usize];
-            grid.data[pos.y as usize][pos.x as usize] = '.';
+        Some(b'#') => false,
+        Some(b'.') => {
+            grid.set(next_pos, grid[pos]);
+            grid.set(pos, b'.');
             true
         }
-        Some('O') => {
+        Some(b'O') => {
             if do_move(grid, next_pos, direction) {
-                grid.data[next_pos.y as usize][next_pos.x as usize] =
-                    grid.data[pos.y as usize][pos.x as usize];
-                grid.data[pos.y as usize][pos.x as usize] = '.';
+                grid.set(next_pos, grid[pos]);
+                grid.set(pos, b'.');
                 true
             } else {
                 false
@@ -52,48 +49,48 @@ fn do_move(grid: &mut Grid, pos: Vec2<isize>, direction: Direction) -> bool {
     }
 }
 
-fn part1(input: &str) -> usize {
-    let (mut grid, moves) = parse(input);
+fn part1((grid, moves): &(Grid, Vec<Direction>)) -> usize {
+    let mut grid = grid.clone();
+    grid.build_index();
     // println!("{grid}");
-    for mov in moves {
-        let (robot_pos, _) = grid.iter_positions().find(|(_, c)| *c == '@').unwrap();
-        let robot_pos = Vec2 {
-            x: robot_pos.0 as isize,
-            y: robot_pos.1 as isize,
-        };
+    for &mov in moves {
+        let robot_pos = grid.find(&b'@').unwrap();
         do_move(&mut grid, robot_pos, mov);
         // println!("{grid}");
     }
     grid.iter_positions()
-        .map(|((x, y), c)| if c == 'O' { x + 100 * y } else { 0 })
+        .map(|((x, y), c)| if c == b'O' { x + 100 * y } else { 0 })
         .sum()
 }
 
-#[test]
-fn test_part1() {
-    assert_eq!(part1(TEST_INPUT_SMALL), 2028);
-    assert_eq!(part1(TEST_INPUT_LARGE), 10092);
+/// The warehouse with the robot in red and the boxes in cyan, for logging to stderr.
+fn show(grid: &Grid) -> Render<'_, u8> {
+    let boxes = grid.find_all(|&c| matches!(c, b'O' | b'[' | b']'));
+    grid.render()
+        .overlay(Overlay::new(boxes).color(Color::Cyan))
+        .overlay(Overlay::new(grid.find(&b'@')).color(Color::Red))
+        .color_for(&io::stderr())
 }
 
 fn widen(grid: Grid) -> Grid {
-    let mut result: Grid = Grid::of('.', grid.rect().w * 2, grid.rect().h);
+    let mut result: Grid = Grid::of(b'.', grid.rect().w * 2, grid.rect().h);
     for ((x, y), c) in grid.iter_positions() {
         match c {
-            '#' => {
-                result.data[y][x * 2] = '#';
-                result.data[y][x * 2 + 1] = '#';
+            b'#' => {
+                result[(x * 2, y)] = b'#';
+                result[(x * 2 + 1, y)] = b'#';
             }
-            'O' => {
-                result.data[y][x * 2] = '[';
-                result.data[y][x * 2 + 1] = ']';
+            b'O' => {
+                result[(x * 2, y)] = b'[';
+                result[(x * 2 + 1, y)] = b']';
             }
-            '.' => {
-                result.data[y][x * 2] = '.';
-                result.data[y][x * 2 + 1] = '.';
+            b'.' => {
+                result[(x * 2, y)] = b'.';
+                result[(x * 2 + 1, y)] = b'.';
             }
-            '@' => {
-                result.data[y][x * 2] = '@';
-                result.data[y][x * 2 + 1] = '.';
+            b'@' => {
+                result[(x * 2, y)] = b'@';
+                result[(x * 2 + 1, y)] = b'.';
             }
             _ => panic!("unknown char in grid"),
         }
@@ -105,23 +102,23 @@ fn can_move2(grid: &Grid, pos: Vec2<isize>, direction: Direction) -> bool {
     let next_pos = pos + direction.as_vec2();
     match grid.grid_get(pos.x, pos.y) {
         None => false,
-        Some('#') => false,
-        Some('.') => true,
-        Some('[') => match direction {
+        Some(b'#') => false,
+        Some(b'.') => true,
+        Some(b'[') => match direction {
             Direction::Left | Direction::Right => can_move2(grid, next_pos, direction),
             Direction::Up | Direction::Down => {
                 can_move2(grid, next_pos, direction)
                     && can_move2(grid, next_pos + Vec2 { x: 1, y: 0 }, direction)
             }
         },
-        Some(']') => match direction {
+        Some(b']') => match direction {
             Direction::Left | Direction::Right => can_move2(grid, next_pos, direction),
             Direction::Up | Direction::Down => {
                 can_move2(grid, next_pos, direction)
                     && can_move2(grid, next_pos + Vec2 { x: -1, y: 0 }, direction)
             }
         },
-        Some('@') => can_move2(grid, next_pos, direction),
+        Some(b'@') => can_move2(grid, next_pos, direction),
         _ => panic!("unknown character in grid"),
     }
 }
@@ -129,76 +126,78 @@ fn can_move2(grid: &Grid, pos: Vec2<isize>, direction: Direction) -> bool {
 fn do_move2(grid: &mut Grid, pos: Vec2<isize>, direction: Direction) {
     let next_pos = pos + direction.as_vec2();
     let c = grid.grid_get(pos.x, pos.y).unwrap();
-    println!("{c} {pos:?} {next_pos:?} {direction:?}");
+    trace!("{} {pos:?} {next_pos:?} {direction:?}", c as char);
     match direction {
         Direction::Left | Direction::Right => match grid.grid_get(next_pos.x, next_pos.y) {
-            Some('.') => {
-                grid.data[next_pos.y as usize][next_pos.x as usize] = c;
-                grid.data[pos.y as usize][pos.x as usize] = '.';
+            Some(b'.') => {
+                grid.set(next_pos, c);
+                grid.set(pos, b'.');
             }
-            Some('[') | Some(']') => {
+            Some(b'[') | Some(b']') => {
                 do_move2(grid, next_pos, direction);
-                grid.data[next_pos.y as usize][next_pos.x as usize] = c;
-                grid.data[pos.y as usize][pos.x as usize] = '.';
+                grid.set(next_pos, c);
+                grid.set(pos, b'.');
             }
             _ => panic!("do_move2 was called but can't move"),
         },
         Direction::Up | Direction::Down => {
             match grid.grid_get(next_pos.x, next_pos.y) {
-                Some('.') => {
-                    grid.data[next_pos.y as usize][next_pos.x as usize] = c;
-                    grid.data[pos.y as usize][pos.x as usize] = '.';
+                Some(b'.') => {
+                    grid.set(next_pos, c);
+                    grid.set(pos, b'.');
                 }
-                Some('[') | Some(']') => {
+                Some(b'[') | Some(b']') => {
                     do_move2(grid, next_pos, direction);
-                    println!("{grid:?}");
-                    grid.data[next_pos.y as usize][next_pos.x as usize] = c;
-                    grid.data[pos.y as usize][pos.x as usize] = '.';
+                    trace!("{grid:?}");
+                    grid.set(next_pos, c);
+                    grid.set(pos, b'.');
                 }
                 _ => panic!("do_move2 was called but can't move"),
             }
-            if c == '[' && grid.grid_get(next_pos.x, next_pos.y) != Some('[') {
+            if c == b'[' && grid.grid_get(next_pos.x, next_pos.y) != Some(b'[') {
                 do_move2(grid, pos + Vec2 { x: 1, y: 0 }, direction);
-            } else if c == ']' && grid.grid_get(next_pos.x, next_pos.y) != Some(']') {
+            } else if c == b']' && grid.grid_get(next_pos.x, next_pos.y) != Some(b']') {
                 do_move2(grid, pos + Vec2 { x: -1, y: 0 }, direction);
-            } else if c == '@' && grid.grid_get(next_pos.x, next_pos.y) == Some('[') {
+            } else if c == b'@' && grid.grid_get(next_pos.x, next_pos.y) == Some(b'[') {
                 do_move2(grid, pos + Vec2 { x: 1, y: 0 }, direction);
-            } else if c == '@' && grid.grid_get(next_pos.x, next_pos.y) == Some(']') {
+            } else if c == b'@' && grid.grid_get(next_pos.x, next_pos.y) == Some(b']') {
                 do_move2(grid, pos + Vec2 { x: -1, y: 0 }, direction);
             }
         }
     }
 }
 
-#[allow(dead_code)]
-fn part2(input: &str) -> usize {
-    let (grid, moves) = parse(input);
-    let mut grid = widen(grid);
-    println!("{grid}");
-    for mov in moves {
-        let (robot_pos, _) = grid.iter_positions().find(|(_, c)| *c == '@').unwrap();
-        let robot_pos = Vec2 {
-            x: robot_pos.0 as isize,
-            y: robot_pos.1 as isize,
-        };
-        println!("{mov:?}");
+fn part2((grid, moves): &(Grid, Vec<Direction>)) -> usize {
+    let mut grid = widen(grid.clone());
+    grid.build_index();
+    debug!("{}", show(&grid));
+    for &mov in moves {
+        let robot_pos = grid.find(&b'@').unwrap();
+        trace!("{mov:?}");
         if can_move2(&grid, robot_pos, mov) {
             do_move2(&mut grid, robot_pos, mov);
         }
-        println!("{grid}");
+        trace!("{}", show(&grid));
     }
     todo!();
 }
 
-#[test]
-#[ignore]
-fn test_part2() {
-    assert_eq!(part2(TEST_INPUT_LARGE), 9021);
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    type Input = (Grid, Vec<Direction>);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
 }
 
 fn main() {
-    let mut input = String::new();
-    stdin().read_to_string(&mut input).unwrap();
-    println!("Part 1: {}", part1(&input));
-    // println!("Part 2: {}", part2(&input));
+    advent_of_code_2024::run::<Day15>();
 }
@@ -1,66 +1,53 @@
-use std::io::{stdin, Read};
+use advent_of_code_2024::parse::{end_of, parse_at};
+use advent_of_code_2024::{debug, trace, Answer, ParseError, Solution};
 
-#[cfg(test)]
-static TEST_INPUT: &str = "Register A: 729
-Register B: 0
-Register C: 0
-
-Program: 0,1,5,4,3,0";
-
-#[cfg(test)]
-static TEST_INPUT2: &str = "Register A: 2024
-Register B: 0
-Register C: 0
-
-Program: 0,3,5,4,3,0";
-
-#[derive(Debug)]
-struct Registers {
+#[derive(Debug, Clone)]
+pub struct Registers {
     a: usize,
     b: usize,
     c: usize,
     pc: usize,
 }
 
-fn parse(input: &str) -> (Registers, Vec<usize>) {
-    fn next_number(input: &mut &str) -> Option<usize> {
-        while input.chars().next().is_some_and(|c| !c.is_ascii_digit()) {
-            *input = &input[1..];
-        }
-        let mut end = 0;
-        while end < input.len() {
-            if input[end..end + 1]
-                .chars()
-                .next()
-                .is_some_and(|c| c.is_ascii_digit())
-            {
-                end += 1;
-            } else {
-                break;
-            }
+fn parse(input: &str) -> Result<(Registers, Vec<usize>), ParseError> {
+    fn next_number<'a>(input: &mut &'a str) -> Option<&'a str> {
+        while let Some(c) = input.chars().next().filter(|c| !c.is_ascii_digit()) {
+            *input = &input[c.len_utf8()..];
         }
+        let end = input
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(input.len());
         if end > 0 {
             let token = &input[0..end];
             *input = &input[end..];
-            token.parse().ok()
+            Some(token)
         } else {
             None
         }
     }
+    let number = |ptr: &mut &str| -> Result<usize, ParseError> {
+        parse_at(
+            input,
+            next_number(ptr).unwrap_or(end_of(input)),
+            "register value",
+        )
+    };
     let mut ptr: &str = input;
     let registers = Registers {
-        a: next_number(&mut ptr).unwrap(),
-        b: next_number(&mut ptr).unwrap(),
-        c: next_number(&mut ptr).unwrap(),
+        a: number(&mut ptr)?,
+        b: number(&mut ptr)?,
+        c: number(&mut ptr)?,
         pc: 0,
     };
-    let (_, program) = input.split_once("Program: ").unwrap();
+    let Some((_, program)) = input.split_once("Program: ") else {
+        return Err(ParseError::at(input, end_of(input), "\"Program: \""));
+    };
     let program = program
         .trim()
         .split(",")
-        .map(|x| x.parse().unwrap())
-        .collect();
-    (registers, program)
+        .map(|x| parse_at(input, x, "3-bit number"))
+        .collect::<Result<_, _>>()?;
+    Ok((registers, program))
 }
 
 fn combo_value(registers: &Registers, operand: usize) -> usize {
@@ -73,12 +60,12 @@ fn combo_value(registers: &Registers, operand: usize) -> usize {
     }
 }
 
-fn part1(input: &str) -> String {
-    let (mut registers, program) = parse(input);
-    println!("{registers:?} {program:?}");
+fn part1((registers, program): &(Registers, Vec<usize>)) -> String {
+    let mut registers = registers.clone();
+    debug!("{registers:?} {program:?}");
     let mut output: Vec<usize> = vec![];
     while registers.pc < program.len() {
-        step(&program, &mut registers, &mut output);
+        step(program, &mut registers, &mut output);
     }
     output
         .iter()
@@ -93,65 +80,65 @@ fn step(program: &[usize], registers: &mut Registers, output: &mut Vec<usize>) {
         0 => {
             // adv
             let combo = program[registers.pc + 1];
-            println!("adv {combo}");
+            trace!("adv {combo}");
             registers.a >>= combo_value(registers, combo);
             registers.pc += 2;
         }
         1 => {
             // bxl
             let literal = program[registers.pc + 1];
-            println!("bxl {literal}");
+            trace!("bxl {literal}");
             registers.b ^= literal;
             registers.pc += 2;
         }
         2 => {
             // bst
             let combo = program[registers.pc + 1];
-            println!("bst {combo}");
+            trace!("bst {combo}");
             registers.b = combo_value(&*registers, combo) % 8;
             registers.pc += 2;
         }
         3 => {
             // jnz
             if registers.a == 0 {
-                println!("jnz (a==0)");
+                trace!("jnz (a==0)");
                 registers.pc += 2;
             } else {
                 let literal = program[registers.pc + 1];
-                println!("jnz {literal} (a!=0)");
+                trace!("jnz {literal} (a!=0)");
                 registers.pc = literal;
             }
         }
         4 => {
             // bxc
-            println!("bxc");
+            trace!("bxc");
             registers.b ^= registers.c;
             registers.pc += 2;
         }
         5 => {
             // out
             let combo = program[registers.pc + 1];
-            println!("out {combo}");
+            trace!("out {combo}");
             output.push(combo_value(&*registers, combo) % 8);
             registers.pc += 2;
         }
         6 => {
             // bdv
             let combo = program[registers.pc + 1];
-            println!("bdv {combo}");
+            trace!("bdv {combo}");
             registers.b = registers.a >> combo_value(&*registers, combo);
             registers.pc += 2;
         }
         7 => {
             // cdv
             let combo = program[registers.pc + 1];
-            println!("cdv {combo}");
+            trace!("cdv {combo}");
             registers.c = registers.a >> combo_value(&*registers, combo);
             registers.pc += 2;
         }
         _ => panic!("illegal opcode"),
     }
-    println!("{registers:?} {output:?}");
+    trace!("{registers:?} {output:?}");
 }
 
 #[test]
@@ -163,7 +150,7 @@ fn test_step() {
         pc: 0,
     };
     let mut output: Vec<usize> = vec![];
-    step(&vec![2, 6], &mut registers, &mut output);
+    step(&[2, 6], &mut registers, &mut output);
     assert_eq!(registers.b, 1);
 
     let program = vec![5, 0, 5, 1, 5, 4];
@@ -219,25 +206,26 @@ fn test_step() {
     assert_eq!(registers.b, 44354);
 }
 
-#[test]
-fn test_part1() {
-    assert_eq!(part1(TEST_INPUT), "4,6,3,5,6,3,5,2,1,0");
-}
-
-#[allow(dead_code)]
-fn part2(_input: &str) -> usize {
+fn part2(_input: &(Registers, Vec<usize>)) -> usize {
     todo!();
 }
 
-#[test]
-#[ignore]
-fn test_part2() {
-    assert_eq!(part2(TEST_INPUT2), 0);
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+    type Input = (Registers, Vec<usize>);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
 }
 
 fn main() {
-    let mut input = String::new();
-    stdin().read_to_string(&mut input).unwrap();
-    println!("Part 1: {}", part1(&input));
-    // println!("Part 2: {}", part2(&input));
+    advent_of_code_2024::run::<Day17>();
 }
@@ -0,0 +1,69 @@
+use crate::answers::KnownAnswers;
+use crate::input;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Example inputs live in `examples/dayNN/<name>.txt`, with the expected answers beside them in
+/// `<name>.answers` as lines of `<part> <answer>`. Parts without an expected answer aren't checked.
+pub fn examples_dir(day: u32) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("examples")
+        .join(format!("day{day:02}"))
+}
+
+#[derive(Debug)]
+pub struct Example {
+    pub name: String,
+    pub path: PathBuf,
+    pub input: String,
+    pub answers: KnownAnswers,
+}
+
+/// Finds every example for a day in `dir`, sorted by name. A day with no examples directory has
+/// no examples.
+pub fn discover(dir: &Path, day: u32) -> io::Result<Vec<Example>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err),
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<_>>()?;
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == "txt"));
+    paths.sort();
+
+    let mut examples = vec![];
+    for path in paths {
+        let answers_path = path.with_extension("answers");
+        let answers = match fs::read_to_string(&answers_path) {
+            Ok(text) => KnownAnswers::parse_for_day(&text, day).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {err}", answers_path.display()),
+                )
+            })?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => KnownAnswers::default(),
+            Err(err) => return Err(err),
+        };
+        examples.push(Example {
+            name: path.file_stem().unwrap().to_string_lossy().into_owned(),
+            input: input::normalize(&fs::read_to_string(&path)?),
+            path,
+            answers,
+        });
+    }
+    Ok(examples)
+}
+
+#[test]
+fn test_discover() {
+    let examples = discover(&examples_dir(15), 15).unwrap();
+    let names: Vec<&str> = examples.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, ["large", "small"]);
+    assert_eq!(examples[1].answers.get(15, 1), Some("2028"));
+    assert!(examples[1].input.starts_with("########\n#..O.O.#\n"));
+
+    assert!(discover(&examples_dir(25), 25).unwrap().is_empty());
+}
@@ -0,0 +1,907 @@
+use crate::parse::end_of;
+use crate::{ParseError, Rect, Vec2};
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::Hash;
+use std::mem;
+use std::ops::{Index, IndexMut, Range};
+
+/// A rectangular grid of cells, indexed by `(x, y)` or `Vec2 { x, y }` with `y` counting rows
+/// down from the top. Cells are stored row after row in one contiguous `Vec`, so a lookup is a
+/// single bounds check and each row is a slice. Text grids default to one byte per cell.
+#[derive(Clone)]
+pub struct Grid<T = u8> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    /// Where each value appears, if `build_index` has been called.
+    index: Option<ValueIndex<T>>,
+}
+
+/// The offsets of each value's cells. `set` updates it directly; cells written through a mutable
+/// reference are only known to have changed, so they're marked dirty and looked at when it's used.
+#[derive(Clone)]
+struct ValueIndex<T> {
+    offsets: HashMap<T, BTreeSet<usize>>,
+    dirty: BTreeSet<usize>,
+}
+
+impl<T: PartialEq> PartialEq for Grid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.height == other.height && self.cells == other.cells
+    }
+}
+
+impl<T: Eq> Eq for Grid<T> {}
+
+impl<T: Copy + Into<char>> Debug for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        writeln!(f, "Grid {{")?;
+        for row in self.rows() {
+            writeln!(f, "\t{}", row.iter().map(|&c| c.into()).collect::<String>())?;
+        }
+        write!(f, "}}")?;
+        Ok(())
+    }
+}
+
+impl<T: Copy + Into<char>> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", row.iter().map(|&c| c.into()).collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+/// Why some text isn't a rectangular grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// The input has no cells at all.
+    Empty,
+    /// Line `line` (1-based) is `width` cells wide, unlike the `expected` width of the first.
+    Ragged {
+        line: usize,
+        width: usize,
+        expected: usize,
+    },
+    /// A byte grid's input has `found` at `line` and `column` (both 1-based), which isn't ASCII.
+    NonAscii {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+}
+
+impl GridError {
+    /// The error as a `ParseError` located in `input`, the text the grid was parsed from.
+    pub fn at(&self, input: &str) -> ParseError {
+        match *self {
+            GridError::Empty => ParseError::at(input, end_of(input), "a grid"),
+            GridError::Ragged { line, expected, .. } => {
+                let found = input
+                    .lines()
+                    .nth(line - 1)
+                    .expect("ragged line is in the input");
+                ParseError::at(input, found, format!("a line of {expected} cells"))
+            }
+            GridError::NonAscii { line, column, .. } => {
+                let line = input
+                    .lines()
+                    .nth(line - 1)
+                    .expect("non-ASCII line is in the input");
+                let (start, c) = line
+                    .char_indices()
+                    .nth(column - 1)
+                    .expect("non-ASCII character is in the line");
+                let found = &line[start..start + c.len_utf8()];
+                ParseError::at(input, found, "an ASCII character")
+            }
+        }
+    }
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid is empty"),
+            GridError::Ragged {
+                line,
+                width,
+                expected,
+            } => write!(
+                f,
+                "line {line} is {width} cells wide but the first line is {expected}"
+            ),
+            GridError::NonAscii {
+                line,
+                column,
+                found,
+            } => write!(
+                f,
+                "line {line}, column {column}: {found:?} is not ASCII, so can't be a byte grid cell"
+            ),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+impl Grid {
+    /// Builds a byte grid from the lines of `input`, one byte per character. Empty input gives an
+    /// empty grid.
+    ///
+    /// # Panics
+    ///
+    /// If the lines aren't all the same length, or a character isn't ASCII and so has no byte.
+    pub fn from(input: &str) -> Grid {
+        match Grid::try_from_str(input) {
+            Ok(grid) => grid,
+            Err(GridError::Empty) => Grid::empty(),
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Builds a byte grid from the lines of `input`, one byte per character, checking that it is
+    /// a non-empty rectangle of ASCII.
+    pub fn try_from_str(input: &str) -> Result<Grid, GridError> {
+        if let Some((line, column, found)) = input.lines().enumerate().find_map(|(y, line)| {
+            let (x, c) = line.chars().enumerate().find(|(_, c)| !c.is_ascii())?;
+            Some((y + 1, x + 1, c))
+        }) {
+            return Err(GridError::NonAscii {
+                line,
+                column,
+                found,
+            });
+        }
+        let (width, height) = dimensions(input)?;
+        let mut cells = Vec::with_capacity(width * height);
+        for line in input.lines() {
+            cells.extend_from_slice(line.as_bytes());
+        }
+        Ok(Grid {
+            cells,
+            width,
+            height,
+            index: None,
+        })
+    }
+
+    /// The cell at `pos` as the character it was parsed from, for code written against grids of
+    /// `char`.
+    pub fn get_char(&self, pos: Vec2<isize>) -> Option<char> {
+        self.get(pos).map(|&cell| cell.into())
+    }
+
+    /// The cell at `(x, y)` as the character it was parsed from, like `grid_get` for grids of
+    /// `char`.
+    pub fn grid_get_char(&self, x: isize, y: isize) -> Option<char> {
+        self.grid_get(x, y).map(char::from)
+    }
+}
+
+/// The width and height of the grid in `input`, counting one cell per character.
+fn dimensions(input: &str) -> Result<(usize, usize), GridError> {
+    let width = |line: &str| line.chars().count();
+    let mut lines = input.lines();
+    let expected = lines.next().map_or(0, &width);
+    if expected == 0 && input.lines().all(str::is_empty) {
+        return Err(GridError::Empty);
+    }
+    let mut height = 1;
+    for line in lines {
+        height += 1;
+        if width(line) != expected {
+            return Err(GridError::Ragged {
+                line: height,
+                width: width(line),
+                expected,
+            });
+        }
+    }
+    Ok((expected, height))
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from the lines of `input`, converting each character with `f`. Empty input
+    /// gives an empty grid.
+    ///
+    /// # Panics
+    ///
+    /// If the lines aren't all the same length.
+    pub fn parse_with(input: &str, f: impl FnMut(char) -> T) -> Grid<T> {
+        match Grid::try_parse_with(input, f) {
+            Ok(grid) => grid,
+            Err(GridError::Empty) => Grid::empty(),
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    fn empty() -> Grid<T> {
+        Grid {
+            cells: Vec::new(),
+            width: 0,
+            height: 0,
+            index: None,
+        }
+    }
+
+    /// Builds a grid from the lines of `input`, converting each character with `f`, checking that
+    /// it is a non-empty rectangle.
+    pub fn try_parse_with(input: &str, f: impl FnMut(char) -> T) -> Result<Grid<T>, GridError> {
+        let (width, height) = dimensions(input)?;
+        Ok(Grid {
+            cells: input.lines().flat_map(str::chars).map(f).collect(),
+            width,
+            height,
+            index: None,
+        })
+    }
+
+    pub fn of(value: T, w: usize, h: usize) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; w * h],
+            width: w,
+            height: h,
+            index: None,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn offset(&self, x: isize, y: isize) -> Option<usize> {
+        let x: usize = x.try_into().ok()?;
+        let y: usize = y.try_into().ok()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    pub fn get(&self, pos: Vec2<isize>) -> Option<&T> {
+        let offset = self.offset(pos.x, pos.y)?;
+        Some(&self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, pos: Vec2<isize>) -> Option<&mut T> {
+        let offset = self.offset(pos.x, pos.y)?;
+        self.touch(offset..offset + 1);
+        Some(&mut self.cells[offset])
+    }
+
+    pub fn grid_get(&self, x: isize, y: isize) -> Option<T>
+    where
+        T: Copy,
+    {
+        Some(self.cells[self.offset(x, y)?])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        let row = y * self.width..(y + 1) * self.width;
+        self.touch(row.clone());
+        &mut self.cells[row]
+    }
+
+    /// Marks cells about to be written through a mutable reference as dirty in the index, if
+    /// there is one, since what's written there can't be seen until it's next used.
+    fn touch(&mut self, offsets: Range<usize>) {
+        if let Some(index) = &mut self.index {
+            index.dirty.extend(offsets);
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Every cell, row after row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Converts every cell with `f`, keeping the shape.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+            index: None,
+        }
+    }
+
+    pub fn subgrid(&self, x: usize, y: usize, w: usize, h: usize) -> Option<Self>
+    where
+        T: Clone,
+    {
+        if x + w > self.width || y + h > self.height {
+            return None;
+        }
+        let mut cells = Vec::with_capacity(w * h);
+        for row in y..y + h {
+            cells.extend_from_slice(&self.row(row)[x..x + w]);
+        }
+        Some(Grid {
+            cells,
+            width: w,
+            height: h,
+            index: None,
+        })
+    }
+
+    pub fn iter_positions(&self) -> GridIterator<'_, T> {
+        GridIterator {
+            grid: self,
+            offset: 0,
+        }
+    }
+
+    fn position(&self, offset: usize) -> Vec2<isize> {
+        Vec2 {
+            x: (offset % self.width) as isize,
+            y: (offset / self.width) as isize,
+        }
+    }
+
+    /// The first cell holding `value`, in reading order.
+    pub fn find(&self, value: &T) -> Option<Vec2<isize>>
+    where
+        T: Hash + Eq,
+    {
+        match &self.index {
+            Some(index) => Some(self.position(*self.indexed(index, value).first()?)),
+            None => Some(self.position(self.cells.iter().position(|c| c == value)?)),
+        }
+    }
+
+    /// Every cell matching `predicate`, in reading order.
+    pub fn find_all<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Vec2<isize>> + 'a {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(move |(_, c)| predicate(c))
+            .map(|(offset, _)| self.position(offset))
+    }
+
+    /// Every cell holding `value`, in reading order. Uses the index if there is one.
+    pub fn positions_of(&self, value: &T) -> Vec<Vec2<isize>>
+    where
+        T: Hash + Eq,
+    {
+        match &self.index {
+            Some(index) => self
+                .indexed(index, value)
+                .into_iter()
+                .map(|o| self.position(o))
+                .collect(),
+            None => self.find_all(|c| c == value).collect(),
+        }
+    }
+
+    /// The offsets of the cells holding `value` according to `index`, in order, checking the
+    /// dirty cells' current values rather than trusting what the index last saw there.
+    fn indexed(&self, index: &ValueIndex<T>, value: &T) -> Vec<usize>
+    where
+        T: Hash + Eq,
+    {
+        let clean = index
+            .offsets
+            .get(value)
+            .into_iter()
+            .flatten()
+            .filter(|o| !index.dirty.contains(o));
+        let dirty = index.dirty.iter().filter(|&&o| self.cells[o] == *value);
+        let mut offsets: Vec<usize> = clean.chain(dirty).copied().collect();
+        offsets.sort_unstable();
+        offsets
+    }
+
+    /// Indexes where each value appears, so `find` and `positions_of` don't scan the grid. Writing
+    /// cells with `set` keeps it current; cells written any other way, through `get_mut`,
+    /// `row_mut` or indexing, are checked each time it's used until the next `set` catches up on
+    /// them, so write with `set` where it matters.
+    pub fn build_index(&mut self)
+    where
+        T: Hash + Eq + Clone,
+    {
+        let mut offsets: HashMap<T, BTreeSet<usize>> = HashMap::new();
+        for (offset, value) in self.cells.iter().enumerate() {
+            offsets.entry(value.clone()).or_default().insert(offset);
+        }
+        self.index = Some(ValueIndex {
+            offsets,
+            dirty: BTreeSet::new(),
+        });
+    }
+
+    /// Writes a cell, updating the index if there is one. Panics if `pos` is outside the grid.
+    pub fn set(&mut self, pos: Vec2<isize>, value: T)
+    where
+        T: Hash + Eq + Clone,
+    {
+        let offset = self
+            .offset(pos.x, pos.y)
+            .unwrap_or_else(|| panic!("position {pos:?} is outside the grid"));
+        if let Some(index) = &mut self.index {
+            // the dirty cells could hold anything, so take them out wherever they were
+            for dirty in mem::take(&mut index.dirty) {
+                for offsets in index.offsets.values_mut() {
+                    offsets.remove(&dirty);
+                }
+                let value = self.cells[dirty].clone();
+                index.offsets.entry(value).or_default().insert(dirty);
+            }
+            let old = &self.cells[offset];
+            if *old == value {
+                return;
+            }
+            if let Some(offsets) = index.offsets.get_mut(old) {
+                offsets.remove(&offset);
+            }
+            index
+                .offsets
+                .entry(value.clone())
+                .or_default()
+                .insert(offset);
+        }
+        self.cells[offset] = value;
+    }
+
+    /// The in-bounds cells up, right, down and left of `pos`, in that order.
+    pub fn neighbours4(&self, pos: Vec2<isize>) -> impl Iterator<Item = (Vec2<isize>, &T)> {
+        self.cells_at(pos, &NEIGHBOURS4)
+    }
+
+    /// The in-bounds cells around `pos`, including diagonals, clockwise from up.
+    pub fn neighbours8(&self, pos: Vec2<isize>) -> impl Iterator<Item = (Vec2<isize>, &T)> {
+        self.cells_at(pos, &NEIGHBOURS8)
+    }
+
+    fn cells_at<'a>(
+        &'a self,
+        pos: Vec2<isize>,
+        offsets: &'a [Vec2<isize>],
+    ) -> impl Iterator<Item = (Vec2<isize>, &'a T)> {
+        offsets.iter().filter_map(move |&offset| {
+            let next = pos + offset;
+            Some((next, self.get(next)?))
+        })
+    }
+
+    /// The cells from `pos` onwards in steps of `step`, stopping at the edge of the grid. The
+    /// first item is `pos` itself, if it's in bounds.
+    pub fn ray(&self, pos: Vec2<isize>, step: Vec2<isize>) -> Ray<'_, T> {
+        assert!(step != Vec2 { x: 0, y: 0 }, "a ray needs a non-zero step");
+        Ray {
+            grid: self,
+            pos,
+            step,
+        }
+    }
+
+    /// Every full line of cells running in direction `step`, from the edge where each starts to
+    /// the edge where it ends. With `step` right these are the rows, down the columns, and down
+    /// right or down left the diagonals and anti-diagonals.
+    pub fn lines(&self, step: Vec2<isize>) -> impl Iterator<Item = Ray<'_, T>> {
+        assert!(step != Vec2 { x: 0, y: 0 }, "lines need a non-zero step");
+        (0..self.cells.len())
+            .map(|offset| self.position(offset))
+            .filter(move |&pos| self.get(pos - step).is_none())
+            .map(move |pos| self.ray(pos, step))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = Ray<'_, T>> {
+        self.lines(Vec2 { x: 0, y: 1 })
+    }
+
+    /// The lines running down and to the right.
+    pub fn diagonals(&self) -> impl Iterator<Item = Ray<'_, T>> {
+        self.lines(Vec2 { x: 1, y: 1 })
+    }
+
+    /// The lines running down and to the left.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Ray<'_, T>> {
+        self.lines(Vec2 { x: -1, y: 1 })
+    }
+
+    /// A `width` by `height` grid where each cell `(x, y)` is copied from `source(x, y)` in this one.
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(self[source(x, y)].clone());
+            }
+        }
+        Grid {
+            cells,
+            width,
+            height,
+            index: None,
+        }
+    }
+
+    /// Swaps rows and columns, mirroring the grid along its main diagonal.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Rotates a quarter turn clockwise: the left column becomes the top row.
+    pub fn rotate_cw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let h = self.height;
+        self.remap(self.height, self.width, |x, y| (y, h - 1 - x))
+    }
+
+    pub fn rotate_180(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let (w, h) = (self.width, self.height);
+        self.remap(w, h, |x, y| (w - 1 - x, h - 1 - y))
+    }
+
+    /// Rotates a quarter turn anticlockwise: the top row becomes the left column.
+    pub fn rotate_ccw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let w = self.width;
+        self.remap(self.height, self.width, |x, y| (w - 1 - y, x))
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let w = self.width;
+        self.remap(w, self.height, |x, y| (w - 1 - x, y))
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let h = self.height;
+        self.remap(self.width, h, |x, y| (x, h - 1 - y))
+    }
+
+    pub fn rect(&self) -> Rect {
+        Rect {
+            x: 0,
+            y: 0,
+            w: self.width,
+            h: self.height,
+        }
+    }
+}
+
+impl<T> Index<Vec2<isize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2<isize>) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Vec2<isize>> for Grid<T> {
+    fn index_mut(&mut self, pos: Vec2<isize>) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is outside the grid"))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width, "x = {x} is outside the grid");
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width, "x = {x} is outside the grid");
+        let offset = y * self.width + x;
+        self.touch(offset..offset + 1);
+        &mut self.cells[offset]
+    }
+}
+
+const NEIGHBOURS4: [Vec2<isize>; 4] = [
+    Vec2 { x: 0, y: -1 },
+    Vec2 { x: 1, y: 0 },
+    Vec2 { x: 0, y: 1 },
+    Vec2 { x: -1, y: 0 },
+];
+
+const NEIGHBOURS8: [Vec2<isize>; 8] = [
+    Vec2 { x: 0, y: -1 },
+    Vec2 { x: 1, y: -1 },
+    Vec2 { x: 1, y: 0 },
+    Vec2 { x: 1, y: 1 },
+    Vec2 { x: 0, y: 1 },
+    Vec2 { x: -1, y: 1 },
+    Vec2 { x: -1, y: 0 },
+    Vec2 { x: -1, y: -1 },
+];
+
+/// See [`Grid::ray`].
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    pos: Vec2<isize>,
+    step: Vec2<isize>,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Vec2<isize>, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.pos;
+        let cell = self.grid.get(pos)?;
+        self.pos += self.step;
+        Some((pos, cell))
+    }
+}
+
+pub struct GridIterator<'a, T = u8> {
+    grid: &'a Grid<T>,
+    offset: usize,
+}
+
+impl<T: Copy> Iterator for GridIterator<'_, T> {
+    type Item = ((usize, usize), T);
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = *self.grid.cells.get(self.offset)?;
+        let pos = (self.offset % self.grid.width, self.offset / self.grid.width);
+        self.offset += 1;
+        Some((pos, c))
+    }
+}
+
+#[test]
+fn test_grid() {
+    let mut grid = Grid::from("ab\ncd\n");
+    assert_eq!((grid.width(), grid.height()), (2, 2));
+    assert_eq!(grid[(1, 0)], b'b');
+    assert_eq!(grid[Vec2 { x: 0, y: 1 }], b'c');
+    assert_eq!(grid.get(Vec2 { x: -1, y: 0 }), None);
+    assert_eq!(grid.get(Vec2 { x: 2, y: 0 }), None);
+    assert_eq!(grid.get(Vec2 { x: 1, y: 1 }), Some(&b'd'));
+    assert_eq!(grid.get_char(Vec2 { x: 1, y: 1 }), Some('d'));
+    assert_eq!(grid.grid_get_char(0, 1), Some('c'));
+    assert_eq!(grid.grid_get_char(0, 2), None);
+    grid[Vec2 { x: 1, y: 1 }] = b'e';
+    grid[(0, 0)] = b'z';
+    assert_eq!(grid.row(1), b"ce");
+    assert_eq!(grid.to_string(), "zb\nce\n");
+    assert_eq!(
+        grid.iter_positions().collect::<Vec<_>>(),
+        vec![
+            ((0, 0), b'z'),
+            ((1, 0), b'b'),
+            ((0, 1), b'c'),
+            ((1, 1), b'e')
+        ]
+    );
+    assert_eq!(grid.subgrid(1, 0, 1, 2).unwrap().to_string(), "b\ne\n");
+    assert_eq!(grid.subgrid(1, 1, 2, 1), None);
+
+    let digits = Grid::parse_with("12\n34\n", |c| c.to_digit(10).unwrap());
+    assert_eq!(digits[(1, 1)], 4);
+    assert_eq!(digits.map(|d| d * 10).cells(), [10, 20, 30, 40]);
+    assert_eq!(digits.iter_positions().map(|(_, d)| d).sum::<u32>(), 10);
+    let chars: Grid<char> = Grid::parse_with("xy\n", |c| c);
+    assert_eq!(format!("{chars:?}"), "Grid {\n\txy\n}");
+}
+
+#[test]
+#[should_panic(expected = "is not ASCII")]
+fn test_grid_rejects_non_ascii() {
+    Grid::from("a€\nbc\n");
+}
+
+#[test]
+#[should_panic(expected = "non-zero step")]
+fn test_ray_rejects_zero_step() {
+    Grid::from("ab\n").ray(Vec2 { x: 0, y: 0 }, Vec2 { x: 0, y: 0 });
+}
+
+#[test]
+fn test_neighbours() {
+    let grid = Grid::from("abc\ndef\nghi\n");
+    let cells = |it: &mut dyn Iterator<Item = (Vec2<isize>, &u8)>| -> String {
+        it.map(|(_, &c)| c as char).collect()
+    };
+    assert_eq!(cells(&mut grid.neighbours4(Vec2 { x: 1, y: 1 })), "bfhd");
+    assert_eq!(
+        cells(&mut grid.neighbours8(Vec2 { x: 1, y: 1 })),
+        "bcfihgda"
+    );
+    assert_eq!(cells(&mut grid.neighbours4(Vec2 { x: 0, y: 0 })), "bd");
+    assert_eq!(cells(&mut grid.neighbours8(Vec2 { x: 2, y: 2 })), "fhe");
+    assert_eq!(
+        grid.neighbours4(Vec2 { x: 2, y: 0 }).next(),
+        Some((Vec2 { x: 2, y: 1 }, &b'f'))
+    );
+    assert_eq!(
+        cells(&mut grid.ray(Vec2 { x: 0, y: 0 }, Vec2 { x: 1, y: 1 })),
+        "aei"
+    );
+    assert_eq!(
+        cells(&mut grid.ray(Vec2 { x: 2, y: 1 }, Vec2 { x: -1, y: 0 })),
+        "fed"
+    );
+    assert_eq!(
+        cells(&mut grid.ray(Vec2 { x: 3, y: 0 }, Vec2 { x: -1, y: 0 })),
+        ""
+    );
+}
+
+#[test]
+fn test_find() {
+    let mut grid = Grid::from("#.@\n.#.\n");
+    assert_eq!(grid.find(&b'@'), Some(Vec2 { x: 2, y: 0 }));
+    assert_eq!(grid.find(&b'x'), None);
+    assert_eq!(
+        grid.find_all(|&c| c != b'.').collect::<Vec<_>>(),
+        vec![
+            Vec2 { x: 0, y: 0 },
+            Vec2 { x: 2, y: 0 },
+            Vec2 { x: 1, y: 1 }
+        ]
+    );
+    assert_eq!(
+        grid.positions_of(&b'#'),
+        vec![Vec2 { x: 0, y: 0 }, Vec2 { x: 1, y: 1 }]
+    );
+
+    grid.build_index();
+    grid.set(Vec2 { x: 2, y: 0 }, b'.');
+    grid.set(Vec2 { x: 0, y: 1 }, b'@');
+    assert_eq!(grid.find(&b'@'), Some(Vec2 { x: 0, y: 1 }));
+    assert_eq!(grid.positions_of(&b'.').len(), 3);
+    assert_eq!(grid.to_string(), "#..\n@#.\n");
+
+    // writing through a reference leaves the index in place and its answers right
+    grid[(0, 0)] = b'@';
+    grid[Vec2 { x: 1, y: 1 }] = b'.';
+    assert!(grid.index.is_some());
+    assert_eq!(grid.find(&b'@'), Some(Vec2 { x: 0, y: 0 }));
+    assert_eq!(
+        grid.positions_of(&b'@'),
+        vec![Vec2 { x: 0, y: 0 }, Vec2 { x: 0, y: 1 }]
+    );
+    assert_eq!(grid.positions_of(&b'#'), vec![]);
+    assert_eq!(grid.positions_of(&b'.').len(), 4);
+    *grid.get_mut(Vec2 { x: 2, y: 1 }).unwrap() = b'#';
+    grid.row_mut(0)[1] = b'#';
+    assert_eq!(
+        grid.positions_of(&b'#'),
+        vec![Vec2 { x: 1, y: 0 }, Vec2 { x: 2, y: 1 }]
+    );
+    // and the next `set` catches the index up on them
+    grid.set(Vec2 { x: 2, y: 0 }, b'#');
+    assert!(grid.index.as_ref().unwrap().dirty.is_empty());
+    assert_eq!(grid.positions_of(&b'#').len(), 3);
+    assert_eq!(grid.positions_of(&b'.').len(), 1);
+    assert_eq!(grid.find(&b'@'), Some(Vec2 { x: 0, y: 0 }));
+    assert_eq!(grid.to_string(), "@##\n@.#\n");
+}
+
+#[test]
+fn test_transforms() {
+    let grid = Grid::from("abc\ndef\n");
+    assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+    assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+    assert_eq!(grid.rotate_180().to_string(), "fed\ncba\n");
+    assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+    assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+    assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+    assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    assert_eq!(grid.rotate_cw().rotate_cw(), grid.rotate_180());
+
+    let lines = |lines: &mut dyn Iterator<Item = Ray<'_, u8>>| -> Vec<String> {
+        lines
+            .map(|line| line.map(|(_, &c)| c as char).collect())
+            .collect()
+    };
+    assert_eq!(lines(&mut grid.lines(Vec2 { x: 1, y: 0 })), ["abc", "def"]);
+    assert_eq!(lines(&mut grid.columns()), ["ad", "be", "cf"]);
+    assert_eq!(lines(&mut grid.diagonals()), ["ae", "bf", "c", "d"]);
+    assert_eq!(lines(&mut grid.anti_diagonals()), ["a", "bd", "ce", "f"]);
+}
+
+#[test]
+fn test_try_from_str() {
+    let grid = Grid::try_from_str("ab\ncd\n").unwrap();
+    assert_eq!(grid, Grid::from("ab\ncd"));
+    assert_eq!(Grid::try_from_str(""), Err(GridError::Empty));
+    assert_eq!(Grid::try_from_str("\n\n"), Err(GridError::Empty));
+    let input = "abc\nabc\nab\nabcd\n";
+    let err = Grid::try_from_str(input).unwrap_err();
+    assert_eq!(
+        err,
+        GridError::Ragged {
+            line: 3,
+            width: 2,
+            expected: 3
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "line 3 is 2 cells wide but the first line is 3"
+    );
+    let err = err.at(input);
+    assert_eq!((err.line, err.column, err.found.as_str()), (3, 1, "ab"));
+    assert_eq!(err.expected, "a line of 3 cells");
+    assert_eq!(
+        GridError::Empty.at("").to_string(),
+        "line 1, column 1: expected a grid, found end of input"
+    );
+
+    // widths count characters, whichever constructor is used
+    let err = Grid::try_parse_with("é\nab\n", |c| c).unwrap_err();
+    assert!(matches!(
+        err,
+        GridError::Ragged {
+            line: 2,
+            width: 2,
+            expected: 1
+        }
+    ));
+    let input = "ab\na€\n";
+    let err = Grid::try_from_str(input).unwrap_err();
+    assert_eq!(
+        err,
+        GridError::NonAscii {
+            line: 2,
+            column: 2,
+            found: '€'
+        }
+    );
+    let err = err.at(input);
+    assert_eq!((err.line, err.column, err.found.as_str()), (2, 2, "€"));
+    assert_eq!(err.expected, "an ASCII character");
+
+    // an empty grid still supports everything that doesn't need a cell
+    let empty = Grid::from("");
+    assert_eq!((empty.width(), empty.height()), (0, 0));
+    assert_eq!(empty.rect().w, 0);
+    assert_eq!(empty.iter_positions().count(), 0);
+    assert_eq!(empty.rows().count(), 0);
+    assert_eq!(empty.columns().count(), 0);
+    assert_eq!(empty.subgrid(0, 0, 0, 0), Some(empty.clone()));
+    assert_eq!(empty.subgrid(0, 0, 1, 1), None);
+    assert_eq!(empty.rotate_cw(), empty);
+    assert_eq!(empty.get(Vec2 { x: 0, y: 0 }), None);
+}
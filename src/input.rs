@@ -0,0 +1,48 @@
+use std::fs;
+use std::io::{self, stdin, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+
+/// Where a day's puzzle input lives, e.g. `inputs/input09.txt`.
+pub fn default_path(day: u32) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("inputs")
+        .join(format!("input{day:02}.txt"))
+}
+
+/// Loads a day's puzzle input from `path` if given, otherwise from stdin. If stdin is a terminal
+/// there's nothing piped in, so fall back to the day's file in `inputs/`.
+pub fn load(day: u32, path: Option<&Path>) -> io::Result<String> {
+    let input = match path {
+        Some(path) => read_file(path)?,
+        None if stdin().is_terminal() => read_file(&default_path(day))?,
+        None => {
+            let mut input = String::new();
+            stdin().read_to_string(&mut input)?;
+            input
+        }
+    };
+    Ok(normalize(&input))
+}
+
+fn read_file(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path)
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", path.display())))
+}
+
+/// Converts CRLF line endings to LF and makes the input end in exactly one newline.
+pub fn normalize(input: &str) -> String {
+    let mut result = input.replace("\r\n", "\n");
+    result.truncate(result.trim_end_matches('\n').len());
+    if !result.is_empty() {
+        result.push('\n');
+    }
+    result
+}
+
+#[test]
+fn test_normalize() {
+    assert_eq!(normalize("1 2\r\n3 4\r\n\r\n"), "1 2\n3 4\n");
+    assert_eq!(normalize("2333133121414131402"), "2333133121414131402\n");
+    assert_eq!(normalize("a\n\nb"), "a\n\nb\n");
+    assert_eq!(normalize("\n"), "");
+}
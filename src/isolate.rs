@@ -0,0 +1,145 @@
+use crate::ParseError;
+use std::any::Any;
+use std::fmt::{self, Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Once};
+use std::thread;
+use std::time::Duration;
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Worker threads are named so the panic hook can tell their panics apart from real crashes.
+const WORKER_NAME: &str = "aoc-worker";
+
+/// How many worker threads haven't finished yet, including any left behind by a timeout.
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+/// The number of isolated calls still running, which after `isolate` returns are the ones that
+/// timed out and haven't finished since.
+pub fn running() -> usize {
+    RUNNING.load(Ordering::SeqCst)
+}
+
+/// Why an isolated call didn't return a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// It hit `todo!()` or `unimplemented!()`.
+    Unimplemented,
+    Panicked(String),
+    TimedOut,
+    /// The day's input didn't parse, so neither part could run.
+    Parse(ParseError),
+    /// The day's input couldn't be loaded, so neither part could run.
+    Input(String),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Unimplemented => write!(f, "unimplemented"),
+            Failure::Panicked(msg) => write!(f, "panicked: {msg}"),
+            Failure::TimedOut => write!(f, "timed out"),
+            Failure::Parse(err) => write!(f, "parse error: {err}"),
+            Failure::Input(err) => write!(f, "could not load input: {err}"),
+        }
+    }
+}
+
+impl Failure {
+    fn from_panic(payload: Box<dyn Any + Send>) -> Failure {
+        let msg = if let Some(msg) = payload.downcast_ref::<&str>() {
+            msg.to_string()
+        } else if let Some(msg) = payload.downcast_ref::<String>() {
+            msg.clone()
+        } else {
+            "unknown panic".to_string()
+        };
+        if msg.starts_with("not yet implemented") || msg.starts_with("not implemented") {
+            Failure::Unimplemented
+        } else {
+            Failure::Panicked(msg)
+        }
+    }
+}
+
+/// Runs `f` on a worker thread, catching panics and giving up after `timeout`. A call that times
+/// out can't be stopped, so its thread is left running in the background.
+pub fn isolate<T: Send + 'static>(
+    timeout: Duration,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, Failure> {
+    static QUIET_WORKERS: Once = Once::new();
+    QUIET_WORKERS.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if thread::current().name() != Some(WORKER_NAME) {
+                default_hook(info);
+            }
+        }));
+    });
+
+    // counts the worker until its thread finishes, however it finishes
+    struct Running;
+    impl Drop for Running {
+        fn drop(&mut self) {
+            RUNNING.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    let (tx, rx) = mpsc::channel();
+    RUNNING.fetch_add(1, Ordering::SeqCst);
+    let running = Running;
+    thread::Builder::new()
+        .name(WORKER_NAME.to_string())
+        .spawn(move || {
+            let _running = running;
+            let result = panic::catch_unwind(AssertUnwindSafe(f));
+            // the receiver is gone if we timed out; nobody is waiting for the result
+            let _ = tx.send(result.map_err(Failure::from_panic));
+        })
+        .expect("failed to spawn worker thread");
+    rx.recv_timeout(timeout).unwrap_or(Err(Failure::TimedOut))
+}
+
+#[test]
+fn test_isolate() {
+    let timeout = Duration::from_secs(5);
+    assert_eq!(isolate(timeout, || 42), Ok(42));
+    assert_eq!(
+        isolate(timeout, || -> i32 { todo!() }),
+        Err(Failure::Unimplemented)
+    );
+    assert_eq!(
+        isolate(timeout, || -> i32 { panic!("illegal opcode") }),
+        Err(Failure::Panicked("illegal opcode".to_string()))
+    );
+    let n = 7;
+    assert_eq!(
+        isolate(timeout, move || -> i32 { panic!("bad value {n}") }),
+        Err(Failure::Panicked("bad value 7".to_string()))
+    );
+    assert_eq!(
+        isolate(Duration::from_millis(10), || thread::sleep(
+            Duration::from_secs(1)
+        )),
+        Err(Failure::TimedOut)
+    );
+}
+
+#[test]
+fn test_running() {
+    let (tx, rx) = mpsc::channel::<()>();
+    assert_eq!(
+        isolate(Duration::from_millis(10), move || rx.recv()),
+        Err(Failure::TimedOut)
+    );
+    // other tests may be running isolated calls too, so only the lower bound is certain
+    assert!(running() >= 1);
+    drop(tx);
+    let started = std::time::Instant::now();
+    while running() > 0 && started.elapsed() < Duration::from_secs(5) {
+        thread::sleep(Duration::from_millis(1));
+    }
+    assert_eq!(running(), 0);
+}
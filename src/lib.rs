@@ -1,5 +1,332 @@
+use std::any::Any;
+use std::env;
 use std::fmt::{self, Debug, Display, Formatter};
-use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::num::TryFromIntError;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::path::PathBuf;
+use std::process;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+pub mod answers;
+pub mod bench;
+pub mod fixtures;
+mod grid;
+pub mod input;
+pub mod isolate;
+pub mod json;
+pub mod log;
+pub mod memory;
+pub mod parse;
+mod render;
+pub mod scaffold;
+mod sparse;
+mod torus;
+pub mod watch;
+
+pub use grid::{Grid, GridError, GridIterator, Ray};
+pub use parse::ParseError;
+pub use render::{Color, Overlay, Render};
+pub use sparse::SparseGrid;
+pub use torus::Torus;
+
+use isolate::{isolate, Failure};
+use json::Json;
+use memory::AllocStats;
+
+/// The answer to one part of a puzzle. Parts return whatever is natural for them and convert
+/// with `.into()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Str(String),
+    /// The part doesn't produce a value, e.g. the answer has to be read off printed output.
+    None,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Str(s) => write!(f, "{s}"),
+            Answer::None => Ok(()),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Int(n.try_into().expect("answer out of range"))
+            }
+        })*
+    };
+}
+
+answer_from_int!(i32, i64, isize, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Str(s.to_string())
+    }
+}
+
+impl From<()> for Answer {
+    fn from(_: ()) -> Self {
+        Answer::None
+    }
+}
+
+#[test]
+fn test_answer() {
+    assert_eq!(Answer::from(42usize), Answer::Int(42));
+    assert_eq!(Answer::from("4,6,3").to_string(), "4,6,3");
+    assert_eq!(Answer::from(()).to_string(), "");
+}
+
+/// One day's puzzle. The input is parsed once and shared by both parts.
+pub trait Solution {
+    const DAY: u32;
+    type Input;
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Answer;
+    fn part2(input: &Self::Input) -> Answer;
+}
+
+/// A day's parsed input, with its type erased. It's shared so parts can run on worker threads.
+#[derive(Clone)]
+pub struct Parsed(Arc<dyn Any + Send + Sync>);
+
+/// A `Solution` with its input type erased, so days can share one dispatch table.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u32,
+    parse: fn(&str) -> Result<Parsed, ParseError>,
+    part1: fn(&Parsed) -> Answer,
+    part2: fn(&Parsed) -> Answer,
+}
+
+impl Day {
+    pub const fn of<S: Solution>() -> Day
+    where
+        S::Input: Send + Sync + 'static,
+    {
+        Day {
+            day: S::DAY,
+            parse: |input| Ok(Parsed(Arc::new(S::parse(input)?))),
+            part1: |input| S::part1(input.0.downcast_ref().expect("input from another day")),
+            part2: |input| S::part2(input.0.downcast_ref().expect("input from another day")),
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Parsed, ParseError> {
+        (self.parse)(input)
+    }
+
+    pub fn part(&self, part: u32, input: &Parsed) -> Answer {
+        match part {
+            1 => (self.part1)(input),
+            2 => (self.part2)(input),
+            _ => panic!("no such part: {part}"),
+        }
+    }
+
+    /// Parses the input and solves each of `parts`, each step on its own worker thread so a panic,
+    /// `todo!()` or runaway part is reported rather than taking down the caller.
+    pub fn solve_isolated(&self, input: &str, parts: &[u32], timeout: Duration) -> Vec<PartResult> {
+        let day = *self;
+        let text = input.to_string();
+        let parsed = isolate(timeout, move || day.parse(&text))
+            .and_then(|parsed| parsed.map_err(Failure::Parse));
+        parts
+            .iter()
+            .map(|&p| {
+                let (result, elapsed) = match parsed.clone() {
+                    Ok(parsed) => {
+                        let start = Instant::now();
+                        let result =
+                            isolate(timeout, move || memory::measure(|| day.part(p, &parsed)));
+                        (result, start.elapsed())
+                    }
+                    Err(failure) => (Err(failure), Duration::ZERO),
+                };
+                let (result, memory) = match result {
+                    Ok((answer, memory)) => (Ok(answer), memory),
+                    Err(failure) => (Err(failure), None),
+                };
+                PartResult {
+                    day: day.day,
+                    part: p,
+                    result,
+                    elapsed,
+                    memory,
+                }
+            })
+            .collect()
+    }
+
+    /// Solves the selected part (or both), printing each answer as it's found, and what it
+    /// allocated if counting is enabled. Each part runs isolated with no time limit, so one that
+    /// hits `todo!()` or panics is reported on stderr and doesn't stop the other. Returns the
+    /// input's parse error, or else the first part that failed other than by being unimplemented.
+    pub fn run(&self, input: &str, part: Option<u32>) -> Result<(), Failure> {
+        let day = *self;
+        let input = self.parse(input).map_err(Failure::Parse)?;
+        let mut failed = None;
+        for p in [1, 2] {
+            if part.is_none_or(|part| part == p) {
+                let input = input.clone();
+                match isolate(Duration::MAX, move || {
+                    memory::measure(|| day.part(p, &input))
+                }) {
+                    Ok((answer, memory)) => {
+                        println!("Part {p}: {answer}");
+                        if let Some(memory) = memory {
+                            println!("  {memory}");
+                        }
+                    }
+                    Err(failure) => {
+                        eprintln!("Part {p}: {failure}");
+                        if failure != Failure::Unimplemented {
+                            failed.get_or_insert(failure);
+                        }
+                    }
+                }
+            }
+        }
+        failed.map_or(Ok(()), Err)
+    }
+}
+
+/// The outcome of running one part, as reported by the runner.
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub day: u32,
+    pub part: u32,
+    pub result: Result<Answer, Failure>,
+    /// Wall time for the part, including the worker thread's overhead but not parsing.
+    pub elapsed: Duration,
+    /// What the part allocated, if counting is enabled and it finished.
+    pub memory: Option<AllocStats>,
+}
+
+impl PartResult {
+    pub fn status(&self) -> &'static str {
+        match self.result {
+            Ok(_) => "ok",
+            Err(Failure::Unimplemented) => "unimplemented",
+            Err(_) => "error",
+        }
+    }
+
+    pub fn to_json(&self) -> Json {
+        let (answer, error) = match &self.result {
+            Ok(Answer::Int(n)) => (Json::Int(*n), Json::Null),
+            Ok(Answer::Str(s)) => (Json::from(s.as_str()), Json::Null),
+            Ok(Answer::None) => (Json::Null, Json::Null),
+            Err(failure) => (Json::Null, Json::from(failure.to_string())),
+        };
+        let mut json = Json::object([
+            ("day", self.day.into()),
+            ("part", self.part.into()),
+            ("status", self.status().into()),
+            ("answer", answer),
+            ("error", error),
+            ("elapsed_ms", (self.elapsed.as_secs_f64() * 1000.0).into()),
+        ]);
+        if let (Json::Object(fields), Some(memory)) = (&mut json, self.memory) {
+            fields.extend([
+                (
+                    "allocations".to_string(),
+                    Json::Int(memory.allocations as i64),
+                ),
+                (
+                    "allocated_bytes".to_string(),
+                    Json::Int(memory.bytes as i64),
+                ),
+                ("peak_bytes".to_string(), Json::Int(memory.peak as i64)),
+            ]);
+        }
+        json
+    }
+}
+
+#[test]
+fn test_run_failures() {
+    struct Broken;
+    impl Solution for Broken {
+        const DAY: u32 = 1;
+        type Input = ();
+        fn parse(input: &str) -> Result<(), ParseError> {
+            match input {
+                "bad" => Err(ParseError::at(input, input, "good")),
+                _ => Ok(()),
+            }
+        }
+        fn part1(_: &()) -> Answer {
+            panic!("crashed")
+        }
+        fn part2(_: &()) -> Answer {
+            todo!()
+        }
+    }
+    let day = Day::of::<Broken>();
+    assert_eq!(day.run("", Some(2)), Ok(()));
+    assert_eq!(
+        day.run("", None),
+        Err(Failure::Panicked("crashed".to_string()))
+    );
+    assert!(matches!(day.run("bad", None), Err(Failure::Parse(_))));
+}
+
+#[test]
+fn test_part_result_json() {
+    let result = PartResult {
+        day: 6,
+        part: 2,
+        result: Err(Failure::Unimplemented),
+        elapsed: Duration::from_micros(1500),
+        memory: None,
+    };
+    assert_eq!(
+        result.to_json().to_string(),
+        r#"{"day":6,"part":2,"status":"unimplemented","answer":null,"error":"unimplemented","elapsed_ms":1.5}"#
+    );
+}
+
+/// Entry point for the per-day binaries: loads the input from the path given as the first
+/// argument (see `input::load` for the fallbacks) and prints both parts.
+pub fn run<S: Solution>()
+where
+    S::Input: Send + Sync + 'static,
+{
+    let path = env::args_os().nth(1).map(PathBuf::from);
+    let input = input::load(S::DAY, path.as_deref()).unwrap_or_else(|err| {
+        eprintln!("could not load input: {err}");
+        process::exit(1);
+    });
+    exit_on_failure(Day::of::<S>().run(&input, None));
+}
+
+/// Exits with an error if `Day::run` failed, rendering a parse error; part failures have already
+/// been reported.
+pub fn exit_on_failure(result: Result<(), Failure>) {
+    match result {
+        Ok(()) => {}
+        Err(Failure::Parse(err)) => {
+            eprintln!("{}", err.render());
+            process::exit(1);
+        }
+        Err(_) => process::exit(1),
+    }
+}
 
 #[test]
 fn test_vec2() {
@@ -50,6 +377,144 @@ impl<T: SubAssign> SubAssign for Vec2<T> {
     }
 }
 
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vec2<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl<T: Div<Output = T> + Copy> Div<T> for Vec2<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x / rhs,
+            y: self.y / rhs,
+        }
+    }
+}
+
+impl<T: MulAssign + Copy> MulAssign<T> for Vec2<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
+impl<T: DivAssign + Copy> DivAssign<T> for Vec2<T> {
+    fn div_assign(&mut self, rhs: T) {
+        self.x /= rhs;
+        self.y /= rhs;
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T: Mul<Output = T> + Add<Output = T> + Sub<Output = T> + Copy> Vec2<T> {
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z component of the 3D cross product: positive if `other` is clockwise from `self`
+    /// on screen, where y points down.
+    pub fn cross(self, other: Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl<T: Neg<Output = T>> Vec2<T> {
+    /// Rotates a quarter turn clockwise on screen, where y points down: up becomes right.
+    pub fn rotate_cw(self) -> Self {
+        Self {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    /// Rotates a quarter turn anticlockwise on screen: up becomes left.
+    pub fn rotate_ccw(self) -> Self {
+        Self {
+            x: self.y,
+            y: -self.x,
+        }
+    }
+}
+
+impl Vec2<isize> {
+    /// Wraps each component into `0..size`, as on a board whose edges join up.
+    pub fn rem_euclid(self, size: Vec2<isize>) -> Self {
+        Self {
+            x: self.x.rem_euclid(size.x),
+            y: self.y.rem_euclid(size.y),
+        }
+    }
+
+    /// The number of orthogonal steps between the two points.
+    pub fn manhattan(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The number of steps between the two points when diagonal steps are allowed.
+    pub fn chebyshev(self, other: Self) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+impl TryFrom<(usize, usize)> for Vec2<isize> {
+    type Error = TryFromIntError;
+
+    fn try_from((x, y): (usize, usize)) -> Result<Self, Self::Error> {
+        Ok(Vec2 {
+            x: x.try_into()?,
+            y: y.try_into()?,
+        })
+    }
+}
+
+impl TryFrom<Vec2<isize>> for (usize, usize) {
+    type Error = TryFromIntError;
+
+    fn try_from(pos: Vec2<isize>) -> Result<Self, Self::Error> {
+        Ok((pos.x.try_into()?, pos.y.try_into()?))
+    }
+}
+
+#[test]
+fn test_vec2_ops() {
+    let v = Vec2 { x: 3, y: -4 };
+    assert_eq!(v * 2, Vec2 { x: 6, y: -8 });
+    assert_eq!(v / 2, Vec2 { x: 1, y: -2 });
+    assert_eq!(-v, Vec2 { x: -3, y: 4 });
+    let mut w = v;
+    w *= 3;
+    w /= -3;
+    assert_eq!(w, -v);
+
+    assert_eq!(v.rem_euclid(Vec2 { x: 2, y: 3 }), Vec2 { x: 1, y: 2 });
+    assert_eq!(v.manhattan(Vec2 { x: 0, y: 0 }), 7);
+    assert_eq!(v.chebyshev(Vec2 { x: 0, y: 0 }), 4);
+    assert_eq!(v.dot(Vec2 { x: 2, y: 1 }), 2);
+    let up = Direction::Up.as_vec2();
+    assert_eq!(up.rotate_cw(), Direction::Right.as_vec2());
+    assert_eq!(up.rotate_ccw(), Direction::Left.as_vec2());
+    assert!(up.cross(up.rotate_cw()) > 0);
+
+    assert_eq!(Vec2::try_from((2usize, 5usize)), Ok(Vec2 { x: 2, y: 5 }));
+    assert_eq!(<(usize, usize)>::try_from(Vec2 { x: 2, y: 5 }), Ok((2, 5)));
+    assert!(<(usize, usize)>::try_from(v).is_err());
+}
+
 pub trait GridTrait
 where
     Self: Sized,
@@ -87,7 +552,7 @@ fn test_grid_trait() {
     assert_eq!(grid.subgrid(0, 0, 4, 4), None);
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Direction {
     Up,
     Right,
@@ -111,6 +576,24 @@ impl Direction {
         }
     }
 
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+
     pub fn as_vec2(self) -> Vec2<isize> {
         match self {
             Direction::Up => Vec2 { x: 0, y: -1 },
@@ -119,96 +602,141 @@ impl Direction {
             Direction::Left => Vec2 { x: -1, y: 0 },
         }
     }
-}
 
-pub struct Grid {
-    pub data: Vec<Vec<char>>,
+    /// The arrow used for this direction in puzzle inputs: one of `^>v<`.
+    pub fn to_char(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
 }
 
-impl Debug for Grid {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
-        writeln!(f, "Grid {{")?;
-        for line in &self.data {
-            writeln!(f, "\t{}", line.iter().cloned().collect::<String>())?;
+impl TryFrom<char> for Direction {
+    /// The character, if it isn't one of `^>v<`.
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' => Ok(Direction::Up),
+            '>' => Ok(Direction::Right),
+            'v' => Ok(Direction::Down),
+            '<' => Ok(Direction::Left),
+            c => Err(c),
         }
-        write!(f, "}}")?;
-        Ok(())
     }
 }
 
-impl Display for Grid {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for line in &self.data {
-            writeln!(f, "{}", line.iter().cloned().collect::<String>())?;
-        }
-        Ok(())
+#[test]
+fn test_direction() {
+    for dir in Direction::DIRECTIONS {
+        assert_eq!(dir.turn_right().turn_left(), dir);
+        assert_eq!(dir.turn_right().turn_right(), dir.opposite());
+        assert_eq!(
+            dir.as_vec2() + dir.opposite().as_vec2(),
+            Vec2 { x: 0, y: 0 }
+        );
+        assert_eq!(Direction::try_from(dir.to_char()), Ok(dir));
     }
+    assert_eq!(Direction::try_from('x'), Err('x'));
+    assert_eq!(Direction8::from(Direction::Left), Direction8::Left);
 }
 
-impl Grid {
-    pub fn from(input: &str) -> Grid {
-        Grid {
-            data: input.lines().map(|line| line.chars().collect()).collect(),
-        }
+/// A direction including diagonals, clockwise from up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    pub const DIRECTIONS: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
     }
-    pub fn of(c: char, w: usize, h: usize) -> Grid {
-        Grid {
-            data: vec![vec![c; w]; h],
-        }
+
+    /// Turns 45 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Direction8::DIRECTIONS[(self.index() + 1) % 8]
     }
-    pub fn grid_get(&self, x: isize, y: isize) -> Option<char> {
-        let x: usize = x.try_into().ok()?;
-        let y: usize = y.try_into().ok()?;
-        Some(*self.data.get(y)?.get(x)?)
+
+    /// Turns 45 degrees anticlockwise.
+    pub fn turn_left(self) -> Self {
+        Direction8::DIRECTIONS[(self.index() + 7) % 8]
     }
-    pub fn subgrid(&self, x: usize, y: usize, w: usize, h: usize) -> Option<Self> {
-        let lines = self.data.get(y..y + h)?;
-        let mut result = vec![];
-        for line in lines {
-            result.push(line.get(x..x + w)?.to_owned());
-        }
-        Some(Grid { data: result })
+
+    pub fn opposite(self) -> Self {
+        Direction8::DIRECTIONS[(self.index() + 4) % 8]
     }
-    pub fn iter_positions(&self) -> GridIterator {
-        GridIterator {
-            grid: self,
-            x: 0,
-            y: 0,
+
+    pub fn as_vec2(self) -> Vec2<isize> {
+        match self {
+            Direction8::Up => Vec2 { x: 0, y: -1 },
+            Direction8::UpRight => Vec2 { x: 1, y: -1 },
+            Direction8::Right => Vec2 { x: 1, y: 0 },
+            Direction8::DownRight => Vec2 { x: 1, y: 1 },
+            Direction8::Down => Vec2 { x: 0, y: 1 },
+            Direction8::DownLeft => Vec2 { x: -1, y: 1 },
+            Direction8::Left => Vec2 { x: -1, y: 0 },
+            Direction8::UpLeft => Vec2 { x: -1, y: -1 },
         }
     }
-    pub fn rect(&self) -> Rect {
-        Rect {
-            x: 0,
-            y: 0,
-            w: self.data[0].len(),
-            h: self.data.len(),
-        }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
     }
 }
 
-pub struct GridIterator<'a> {
-    grid: &'a Grid,
-    x: usize,
-    y: usize,
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::Up => Direction8::Up,
+            Direction::Right => Direction8::Right,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+        }
+    }
 }
 
-impl Iterator for GridIterator<'_> {
-    type Item = ((usize, usize), char);
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.y >= self.grid.data.len() {
-            return None;
-        }
-        let c = self.grid.data[self.y][self.x];
-        let res = Some(((self.x, self.y), c));
-        self.x += 1;
-        if self.x >= self.grid.data[self.y].len() {
-            self.x = 0;
-            self.y += 1;
-        }
-        res
+#[test]
+fn test_direction8() {
+    for dir in Direction8::DIRECTIONS {
+        assert_eq!(dir.turn_right().turn_left(), dir);
+        assert_eq!(
+            dir.turn_right().turn_right().turn_right().turn_right(),
+            dir.opposite()
+        );
+        assert_eq!(
+            dir.as_vec2() + dir.opposite().as_vec2(),
+            Vec2 { x: 0, y: 0 }
+        );
+        assert_eq!(
+            dir.is_diagonal(),
+            dir.as_vec2().x != 0 && dir.as_vec2().y != 0
+        );
     }
+    assert_eq!(Direction8::UpLeft.turn_right(), Direction8::Up);
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Rect {
     pub x: isize,
     pub y: isize,
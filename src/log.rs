@@ -0,0 +1,98 @@
+use std::fmt::Arguments;
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The environment variable read for the log level when none has been set, e.g. `AOC_LOG=trace`.
+pub const ENV_VAR: &str = "AOC_LOG";
+
+/// How much diagnostic output to write to stderr. Each level includes the ones before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    /// Occasional progress and summary output.
+    Debug,
+    /// Output from inner loops, which can be very large.
+    Trace,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "" | "off" => Ok(Level::Off),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!("unknown log level {s:?}")),
+        }
+    }
+}
+
+const UNSET: u8 = u8::MAX;
+
+static LEVEL: AtomicU8 = AtomicU8::new(UNSET);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// The current level: whatever was last set, or else the value of `AOC_LOG`, or `Off`.
+pub fn level() -> Level {
+    match LEVEL.load(Ordering::Relaxed) {
+        UNSET => {
+            let level = std::env::var(ENV_VAR)
+                .ok()
+                .and_then(|var| var.parse().ok())
+                .unwrap_or(Level::Off);
+            set_level(level);
+            level
+        }
+        l if l == Level::Trace as u8 => Level::Trace,
+        l if l == Level::Debug as u8 => Level::Debug,
+        _ => Level::Off,
+    }
+}
+
+pub fn enabled(level: Level) -> bool {
+    level != Level::Off && level <= self::level()
+}
+
+#[doc(hidden)]
+pub fn write(level: Level, args: Arguments<'_>) {
+    let tag = match level {
+        Level::Trace => "trace",
+        _ => "debug",
+    };
+    let _ = writeln!(io::stderr().lock(), "[{tag}] {args}");
+}
+
+/// Writes a line to stderr if the log level is `Debug` or higher. The arguments are only
+/// evaluated when it is.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Debug) {
+            $crate::log::write($crate::log::Level::Debug, format_args!($($arg)*));
+        }
+    };
+}
+
+/// Writes a line to stderr if the log level is `Trace`.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Trace) {
+            $crate::log::write($crate::log::Level::Trace, format_args!($($arg)*));
+        }
+    };
+}
+
+#[test]
+fn test_level() {
+    assert_eq!("TRACE".parse(), Ok(Level::Trace));
+    assert_eq!("debug".parse(), Ok(Level::Debug));
+    assert_eq!("".parse(), Ok(Level::Off));
+    assert!("loud".parse::<Level>().is_err());
+    assert!(Level::Debug < Level::Trace);
+}
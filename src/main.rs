@@ -1,3 +1,732 @@
+use advent_of_code_2024::answers::{self, KnownAnswers, Verdict};
+use advent_of_code_2024::bench::{self, Baseline};
+use advent_of_code_2024::isolate::{self, Failure};
+use advent_of_code_2024::json::Json;
+use advent_of_code_2024::memory::{self, format_bytes, AllocStats, CountingAllocator};
+use advent_of_code_2024::watch::Watcher;
+use advent_of_code_2024::{
+    exit_on_failure, fixtures, input, log, scaffold, Answer, Day, PartResult,
+};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use std::{env, fs, process, thread};
+
+#[allow(dead_code)]
+#[path = "bin/day01.rs"]
+mod day01;
+#[allow(dead_code)]
+#[path = "bin/day02.rs"]
+mod day02;
+#[allow(dead_code)]
+#[path = "bin/day03.rs"]
+mod day03;
+#[allow(dead_code)]
+#[path = "bin/day04.rs"]
+mod day04;
+#[allow(dead_code)]
+#[path = "bin/day05.rs"]
+mod day05;
+#[allow(dead_code)]
+#[path = "bin/day06.rs"]
+mod day06;
+#[allow(dead_code)]
+#[path = "bin/day07.rs"]
+mod day07;
+#[allow(dead_code)]
+#[path = "bin/day08.rs"]
+mod day08;
+#[allow(dead_code)]
+#[path = "bin/day09.rs"]
+mod day09;
+#[allow(dead_code)]
+#[path = "bin/day10.rs"]
+mod day10;
+#[allow(dead_code)]
+#[path = "bin/day11.rs"]
+mod day11;
+#[allow(dead_code)]
+#[path = "bin/day12.rs"]
+mod day12;
+#[allow(dead_code)]
+#[path = "bin/day13.rs"]
+mod day13;
+#[allow(dead_code)]
+#[path = "bin/day14.rs"]
+mod day14;
+#[allow(dead_code)]
+#[path = "bin/day15.rs"]
+mod day15;
+#[allow(dead_code)]
+#[path = "bin/day17.rs"]
+mod day17;
+
+// only counts anything once enabled with `--memory`
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Every implemented day, in order.
+const DAYS: &[Day] = &[
+    Day::of::<day01::Day01>(),
+    Day::of::<day02::Day02>(),
+    Day::of::<day03::Day03>(),
+    Day::of::<day04::Day04>(),
+    Day::of::<day05::Day05>(),
+    Day::of::<day06::Day06>(),
+    Day::of::<day07::Day07>(),
+    Day::of::<day08::Day08>(),
+    Day::of::<day09::Day09>(),
+    Day::of::<day10::Day10>(),
+    Day::of::<day11::Day11>(),
+    Day::of::<day12::Day12>(),
+    Day::of::<day13::Day13>(),
+    Day::of::<day14::Day14>(),
+    Day::of::<day15::Day15>(),
+    Day::of::<day17::Day17>(),
+];
+
+static USAGE: &str = "usage: aoc <day> [--part <1|2>] [--input <path>] [--json <path|->] [--memory]
+       aoc all [--part <1|2>] [--timeout <secs>] [--jobs <n>] [--json <path|->] [--memory]
+       aoc verify [day|all] [--part <1|2>] [--timeout <secs>]
+       aoc bench [day|all] [--part <1|2>] [--iterations <n>] [--save]
+       aoc watch <day> [--part <1|2>] [--input <path>] [--timeout <secs>] [--memory]
+       aoc new-day <day>
+
+  -j, --jobs <n>  solve up to n days at once (default and 0: one per CPU)
+  -m, --memory    report allocations and peak memory for each part
+  -v, -vv         log debug or trace output to stderr (or set AOC_LOG=debug|trace)";
+
+/// Resolves a day argument (a number or `all`) to the matching days.
+fn select_days(arg: &str) -> Vec<&'static Day> {
+    if arg == "all" {
+        return DAYS.iter().collect();
+    }
+    let day: u32 = arg.parse().unwrap_or_else(|_| usage());
+    let Some(day) = DAYS.iter().find(|d| d.day == day) else {
+        eprintln!("day {day} is not implemented");
+        process::exit(1);
+    };
+    vec![day]
+}
+
+fn parts(part: Option<u32>) -> Vec<u32> {
+    match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    }
+}
+
+fn run_day(day: &Day, part: Option<u32>, input_path: Option<&Path>) {
+    let input = input::load(day.day, input_path).unwrap_or_else(|err| {
+        eprintln!("could not load input: {err}");
+        process::exit(1);
+    });
+    exit_on_failure(day.run(&input, part));
+}
+
+/// Writes the results of a run as one JSON document to `path`, or to stdout for `-`. Writing to a
+/// file keeps the document intact even when a day prints to stdout while it runs.
+fn write_json(path: &str, results: &[PartResult], elapsed: Duration) {
+    let doc = Json::object([
+        (
+            "results",
+            Json::Array(results.iter().map(PartResult::to_json).collect()),
+        ),
+        ("elapsed_ms", (elapsed.as_secs_f64() * 1000.0).into()),
+    ]);
+    if path == "-" {
+        println!("{doc}");
+    } else if let Err(err) = fs::write(path, format!("{doc}\n")) {
+        eprintln!("could not write {path}: {err}");
+        process::exit(1);
+    }
+}
+
+/// Solves one day in isolation and writes the results as JSON. Exits with an error if the input
+/// can't be loaded.
+fn run_day_json(
+    day: &Day,
+    part: Option<u32>,
+    input_path: Option<&Path>,
+    timeout: Duration,
+    json: &str,
+) {
+    let input = input::load(day.day, input_path).unwrap_or_else(|err| {
+        eprintln!("could not load input: {err}");
+        process::exit(1);
+    });
+    let start = Instant::now();
+    let results = day.solve_isolated(&input, &parts(part), timeout);
+    write_json(json, &results, start.elapsed());
+}
+
+/// How often `watch` polls for changes.
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Describes one part's result on a line, checked against `answers` if it has one.
+fn describe(result: &PartResult, answers: &KnownAnswers) -> String {
+    let outcome = match &result.result {
+        Ok(answer) => match answers.check(result.day, result.part, answer) {
+            Verdict::Missing => answer.to_string(),
+            verdict => format!("{answer}  {verdict}"),
+        },
+        Err(Failure::Parse(err)) => format!("parse error\n{}", err.render()),
+        Err(failure) => failure.to_string(),
+    };
+    let line = format!(
+        "  Part {}: {outcome}  ({:.2?})",
+        result.part, result.elapsed
+    );
+    match result.memory {
+        Some(memory) => format!("{line}\n    {memory}"),
+        None => line,
+    }
+}
+
+/// Solves a day against its input and every example, and again whenever any of those files
+/// change, redrawing the results each time. Each solve is isolated, so a panic or a part that
+/// never finishes is reported and watching carries on. A part that timed out can't be stopped, so
+/// the next solve waits for it to finish rather than piling up threads that never do.
+fn watch(day: &Day, part: Option<u32>, input_path: Option<&Path>, timeout: Duration) -> ! {
+    let input_path = input_path.map_or_else(|| input::default_path(day.day), Path::to_path_buf);
+    let examples_dir = fixtures::examples_dir(day.day);
+    let mut watcher = Watcher::new(vec![input_path.clone()], vec![examples_dir.clone()]);
+    loop {
+        if isolate::running() > 0 {
+            println!(
+                "\nWaiting for {} timed out part(s) to finish before solving again \
+                 (Ctrl-C to stop)",
+                isolate::running()
+            );
+            while isolate::running() > 0 {
+                thread::sleep(WATCH_INTERVAL);
+            }
+        }
+        let mut out = format!(
+            "Day {:02}: watching {} and {} (Ctrl-C to stop)\n",
+            day.day,
+            input_path.display(),
+            examples_dir.display()
+        );
+        let answers = KnownAnswers::load(&answers::default_path()).unwrap_or_default();
+        out += &format!("\n{}\n", input_path.display());
+        match input::load(day.day, Some(&input_path)) {
+            Ok(input) => {
+                for result in day.solve_isolated(&input, &parts(part), timeout) {
+                    out += &format!("{}\n", describe(&result, &answers));
+                }
+            }
+            Err(err) => out += &format!("  could not load input: {err}\n"),
+        }
+        match fixtures::discover(&examples_dir, day.day) {
+            Ok(examples) => {
+                for example in examples {
+                    out += &format!("\n{}\n", example.path.display());
+                    for result in day.solve_isolated(&example.input, &parts(part), timeout) {
+                        out += &format!("{}\n", describe(&result, &example.answers));
+                    }
+                }
+            }
+            Err(err) => out += &format!("\ncould not load examples: {err}\n"),
+        }
+        // clear the screen and redraw in one write, so it doesn't flicker
+        print!("\x1b[2J\x1b[H{out}");
+        let _ = io::stdout().flush();
+        while !watcher.changed() {
+            thread::sleep(WATCH_INTERVAL);
+        }
+    }
+}
+
+/// Checks each day against `answers.txt`, skipping parts with no recorded answer. Returns false if
+/// anything failed.
+fn verify(days: &[&Day], part: Option<u32>, timeout: Duration) -> bool {
+    let answers = KnownAnswers::load(&answers::default_path()).unwrap_or_else(|err| {
+        eprintln!("could not load answers: {err}");
+        process::exit(1);
+    });
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for day in days {
+        let input = match input::load(day.day, Some(&input::default_path(day.day))) {
+            Ok(input) => input,
+            Err(err) => {
+                println!("Day {:02}: no input ({err})", day.day);
+                missing += parts(part).len();
+                continue;
+            }
+        };
+        let (known, unknown): (Vec<u32>, Vec<u32>) = parts(part)
+            .into_iter()
+            .partition(|&p| answers.get(day.day, p).is_some());
+        for PartResult {
+            part: p, result, ..
+        } in day.solve_isolated(&input, &known, timeout)
+        {
+            match result {
+                Ok(answer) => {
+                    let verdict = answers.check(day.day, p, &answer);
+                    if verdict == Verdict::Pass {
+                        passed += 1;
+                    } else {
+                        failed += 1;
+                    }
+                    println!("Day {:02} part {p}: {verdict}", day.day);
+                }
+                Err(Failure::Parse(err)) => {
+                    failed += 1;
+                    println!("Day {:02} part {p}: FAIL\n{}", day.day, err.render());
+                }
+                Err(failure) => {
+                    failed += 1;
+                    println!("Day {:02} part {p}: FAIL ({failure})", day.day);
+                }
+            }
+        }
+        for p in unknown {
+            missing += 1;
+            println!("Day {:02} part {p}: {}", day.day, Verdict::Missing);
+        }
+    }
+    println!("{passed} passed, {failed} failed, {missing} missing");
+    failed == 0
+}
+
+/// One day's row in the `all` summary.
+struct DayReport {
+    day: u32,
+    /// The result of each part, or why the day couldn't be run at all.
+    results: Result<Vec<PartResult>, Failure>,
+    /// Wall time for the whole day, including loading and parsing the input.
+    elapsed: Duration,
+}
+
+impl DayReport {
+    /// What the day's parts allocated between them, with the larger part's peak.
+    fn memory(&self) -> Option<AllocStats> {
+        let results = self.results.as_ref().ok()?;
+        total_memory(results.iter().filter_map(|result| result.memory))
+    }
+
+    fn status(&self) -> &'static str {
+        match &self.results {
+            Err(_) => "error",
+            Ok(results) => {
+                let statuses: Vec<&str> = results.iter().map(PartResult::status).collect();
+                ["error", "unimplemented"]
+                    .into_iter()
+                    .find(|status| statuses.contains(status))
+                    .unwrap_or("ok")
+            }
+        }
+    }
+}
+
+/// Adds up allocations, keeping the largest peak, or `None` if there's nothing to add up.
+fn total_memory(stats: impl Iterator<Item = AllocStats>) -> Option<AllocStats> {
+    stats.reduce(|a, b| AllocStats {
+        allocations: a.allocations + b.allocations,
+        bytes: a.bytes + b.bytes,
+        peak: a.peak.max(b.peak),
+    })
+}
+
+/// The table's memory columns: none unless counting is enabled.
+fn memory_cells(stats: Option<AllocStats>) -> Vec<String> {
+    match stats {
+        _ if !memory::enabled() => vec![],
+        Some(stats) => vec![stats.allocations.to_string(), format_bytes(stats.peak)],
+        None => vec!["-".to_string(); 2],
+    }
+}
+
+fn solve_day(day: &Day, parts: &[u32], timeout: Duration) -> DayReport {
+    let start = Instant::now();
+    let results = input::load(day.day, Some(&input::default_path(day.day)))
+        .map(|input| day.solve_isolated(&input, parts, timeout))
+        .map_err(|err| Failure::Input(err.to_string()));
+    DayReport {
+        day: day.day,
+        results,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Solves every day on a pool of `jobs` threads, each taking the next unsolved day until none are
+/// left. Nothing is printed until all are done, so output from different days can't interleave.
+fn solve_days(parts: &[u32], timeout: Duration, jobs: usize) -> Vec<DayReport> {
+    let next = AtomicUsize::new(0);
+    let mut reports: Vec<DayReport> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.min(DAYS.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut reports = vec![];
+                    while let Some(day) = DAYS.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        reports.push(solve_day(day, parts, timeout));
+                    }
+                    reports
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    });
+    reports.sort_by_key(|report| report.day);
+    reports
+}
+
+/// Runs every day in isolation, so one that panics, hits `todo!()` or never finishes doesn't stop
+/// the rest, then prints a summary table, or writes the results as JSON if `json` is given.
+fn run_all(part: Option<u32>, timeout: Duration, jobs: usize, json: Option<&str>) {
+    let start = Instant::now();
+    let reports = solve_days(&parts(part), timeout, jobs);
+    let elapsed = start.elapsed();
+    if let Some(json) = json {
+        // a day that couldn't run still gets a record for each part, so it isn't silently missing
+        let results: Vec<PartResult> = reports
+            .into_iter()
+            .flat_map(|report| match report.results {
+                Ok(results) => results,
+                Err(failure) => parts(part)
+                    .into_iter()
+                    .map(|p| PartResult {
+                        day: report.day,
+                        part: p,
+                        result: Err(failure.clone()),
+                        elapsed: Duration::ZERO,
+                        memory: None,
+                    })
+                    .collect(),
+            })
+            .collect();
+        write_json(json, &results, elapsed);
+        return;
+    }
+
+    let mut rows: Vec<Vec<String>> = vec![];
+    for report in &reports {
+        let cells = match &report.results {
+            Ok(results) => results
+                .iter()
+                .map(|result| match &result.result {
+                    Ok(Answer::None) => "-".to_string(),
+                    Ok(answer) => answer.to_string(),
+                    Err(Failure::Parse(err)) => {
+                        eprintln!("Day {:02}: {}", report.day, err.render());
+                        "parse error".to_string()
+                    }
+                    Err(failure) => failure.to_string(),
+                })
+                .collect(),
+            Err(err) => {
+                eprintln!("Day {:02}: {err}", report.day);
+                vec!["no input".to_string(); parts(part).len()]
+            }
+        };
+        rows.push(
+            [
+                vec![format!("{:02}", report.day)],
+                cells,
+                vec![
+                    report.status().to_string(),
+                    format!("{:.2?}", report.elapsed),
+                ],
+                memory_cells(report.memory()),
+            ]
+            .concat(),
+        );
+    }
+    let ok = reports.iter().filter(|r| r.status() == "ok").count();
+    let cpu_time: Duration = reports.iter().map(|r| r.elapsed).sum();
+    rows.push(
+        [
+            vec!["Total".to_string()],
+            vec![String::new(); parts(part).len()],
+            vec![
+                format!("{ok}/{} ok", reports.len()),
+                format!("{cpu_time:.2?}"),
+            ],
+            memory_cells(total_memory(reports.iter().filter_map(DayReport::memory))),
+        ]
+        .concat(),
+    );
+    let header: Vec<String> = ["Day".to_string()]
+        .into_iter()
+        .chain(parts(part).iter().map(|p| format!("Part {p}")))
+        .chain(["Status".to_string(), "Time".to_string()])
+        .chain(
+            memory::enabled()
+                .then(|| ["Allocations".to_string(), "Peak".to_string()])
+                .into_iter()
+                .flatten(),
+        )
+        .collect();
+    print_table(&header, &rows);
+    println!("Wall time {elapsed:.2?} on {jobs} thread(s)");
+}
+
+fn print_table(header: &[String], rows: &[Vec<String>]) {
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([header[i].len()])
+                .max()
+                .unwrap()
+        })
+        .collect();
+    for row in [header].into_iter().chain(rows.iter().map(Vec::as_slice)) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:<width$}"))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+    }
+}
+
+/// Times parsing and each part separately against the real inputs, comparing against the saved
+/// baseline. Parts with no known answer are skipped, since they're unsolved or don't terminate.
+fn bench(days: &[&Day], part: Option<u32>, iterations: usize, save: bool) {
+    let answers = KnownAnswers::load(&answers::default_path()).unwrap_or_default();
+    let baseline_path = bench::default_baseline_path();
+    let mut baseline = Baseline::load(&baseline_path).unwrap_or_else(|err| {
+        eprintln!("could not load baseline: {err}");
+        process::exit(1);
+    });
+    let mut report = |day: u32, stage: &str, stats: bench::Stats| {
+        let change = baseline
+            .compare(day, stage, stats.median)
+            .unwrap_or_default();
+        let line = format!("Day {day:02} {stage:<5}  {stats}  {change}");
+        println!("{}", line.trim_end());
+        baseline.set(day, stage, stats.median);
+    };
+    for day in days {
+        let input = match input::load(day.day, Some(&input::default_path(day.day))) {
+            Ok(input) => input,
+            Err(err) => {
+                println!("Day {:02}: no input ({err})", day.day);
+                continue;
+            }
+        };
+        let parsed = match day.parse(&input) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                println!("Day {:02}: parse failed\n{}", day.day, err.render());
+                continue;
+            }
+        };
+        report(
+            day.day,
+            "parse",
+            bench::measure(iterations, || day.parse(&input)),
+        );
+        for p in parts(part) {
+            let stage = format!("part{p}");
+            if answers.get(day.day, p).is_none() {
+                println!("Day {:02} {stage}  skipped (no known answer)", day.day);
+                continue;
+            }
+            report(
+                day.day,
+                &stage,
+                bench::measure(iterations, || day.part(p, &parsed)),
+            );
+        }
+    }
+    if save {
+        baseline.save(&baseline_path).unwrap_or_else(|err| {
+            eprintln!("could not save baseline: {err}");
+            process::exit(1);
+        });
+        println!("Saved baseline to {}", baseline_path.display());
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("{USAGE}");
+    process::exit(2);
+}
+
 fn main() {
-    println!("Hello, world!");
+    let mut args = env::args().skip(1);
+    let mut positional: Vec<String> = vec![];
+    let mut part: Option<u32> = None;
+    let mut input_path: Option<String> = None;
+    let mut iterations: usize = 10;
+    let mut save = false;
+    let mut timeout = isolate::DEFAULT_TIMEOUT;
+    let mut json: Option<String> = None;
+    let one_per_cpu = || thread::available_parallelism().map_or(1, |n| n.get());
+    let mut jobs = one_per_cpu();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                part = match args.next().and_then(|p| p.parse().ok()) {
+                    Some(p @ (1 | 2)) => Some(p),
+                    _ => usage(),
+                }
+            }
+            "--input" | "-i" => input_path = Some(args.next().unwrap_or_else(|| usage())),
+            "--iterations" | "-n" => {
+                iterations = match args.next().and_then(|n| n.parse().ok()) {
+                    Some(n) if n > 0 => n,
+                    _ => usage(),
+                }
+            }
+            "--save" => save = true,
+            "--jobs" | "-j" => {
+                jobs = match args.next().and_then(|n| n.parse().ok()) {
+                    Some(0) => one_per_cpu(),
+                    Some(n) => n,
+                    None => usage(),
+                }
+            }
+            "--memory" | "-m" => {
+                memory::enable();
+            }
+            "-v" | "--verbose" => log::set_level(log::Level::Debug),
+            "-vv" => log::set_level(log::Level::Trace),
+            "--json" => json = Some(args.next().unwrap_or_else(|| usage())),
+            "--timeout" | "-t" => {
+                timeout = match args.next().and_then(|t| t.parse::<f64>().ok()) {
+                    Some(secs) if secs > 0.0 => Duration::from_secs_f64(secs),
+                    _ => usage(),
+                }
+            }
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                return;
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let positional: Vec<&str> = positional.iter().map(String::as_str).collect();
+    match command(&positional, input_path.is_some()).unwrap_or_else(|| usage()) {
+        Command::Verify(days) => {
+            if !verify(&select_days(days), part, timeout) {
+                process::exit(1);
+            }
+        }
+        Command::Bench(days) => bench(&select_days(days), part, iterations, save),
+        Command::NewDay(day) => {
+            let day: u32 = match day.parse() {
+                Ok(day @ 1..=25) => day,
+                _ => usage(),
+            };
+            let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+            match scaffold::new_day(root, day) {
+                Ok(created) => {
+                    for path in created {
+                        println!("Created {}", path.display());
+                    }
+                    println!("Registered day{day:02} in src/main.rs");
+                }
+                Err(err) => {
+                    eprintln!("could not create day {day}: {err}");
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Watch(day) => {
+            let day = select_days(day)[0];
+            watch(day, part, input_path.as_deref().map(Path::new), timeout);
+        }
+        Command::All => run_all(part, timeout, jobs, json.as_deref()),
+        Command::Day(day) => {
+            let day = select_days(day)[0];
+            let input_path = input_path.as_deref().map(Path::new);
+            match json.as_deref() {
+                Some(json) => run_day_json(day, part, input_path, timeout, json),
+                None => run_day(day, part, input_path),
+            }
+        }
+    }
+}
+
+/// What the positional arguments ask for. Days are still unresolved: `Verify` and `Bench` take a
+/// day or `all`, the others a single day.
+#[derive(Debug, PartialEq, Eq)]
+enum Command<'a> {
+    Verify(&'a str),
+    Bench(&'a str),
+    NewDay(&'a str),
+    Watch(&'a str),
+    All,
+    Day(&'a str),
+}
+
+/// Picks the command for `positional`, or `None` if the arguments don't make sense together. Only
+/// a single day can be given an input file, so `all` with one is rejected rather than taken as a
+/// day.
+fn command<'a>(positional: &[&'a str], has_input: bool) -> Option<Command<'a>> {
+    let days = |rest: &[&'a str]| rest.first().copied().unwrap_or("all");
+    match *positional {
+        ["verify", ref rest @ ..] if rest.len() <= 1 && !has_input => {
+            Some(Command::Verify(days(rest)))
+        }
+        ["bench", ref rest @ ..] if rest.len() <= 1 && !has_input => {
+            Some(Command::Bench(days(rest)))
+        }
+        ["new-day", day] => Some(Command::NewDay(day)),
+        ["watch", "all"] => None,
+        ["watch", day] => Some(Command::Watch(day)),
+        ["all"] if has_input => None,
+        ["all"] => Some(Command::All),
+        [day] => Some(Command::Day(day)),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_command() {
+    assert_eq!(command(&["all"], false), Some(Command::All));
+    assert_eq!(command(&["all"], true), None);
+    assert_eq!(command(&["4"], true), Some(Command::Day("4")));
+    assert_eq!(command(&["verify"], false), Some(Command::Verify("all")));
+    assert_eq!(command(&["verify", "6"], true), None);
+    assert_eq!(command(&["bench", "6"], false), Some(Command::Bench("6")));
+    assert_eq!(command(&["watch", "all"], false), None);
+    assert_eq!(command(&["watch", "6"], true), Some(Command::Watch("6")));
+    assert_eq!(
+        command(&["new-day", "18"], false),
+        Some(Command::NewDay("18"))
+    );
+    assert_eq!(command(&[], false), None);
+    assert_eq!(command(&["6", "7"], false), None);
+}
+
+#[test]
+fn test_examples() {
+    let mut checked = 0;
+    let mut failures: Vec<String> = vec![];
+    for day in DAYS {
+        let examples = fixtures::discover(&fixtures::examples_dir(day.day), day.day).unwrap();
+        for example in examples {
+            let parsed = match day.parse(&example.input) {
+                Ok(parsed) => parsed,
+                Err(err) => {
+                    failures.push(format!("{}: {err}", example.path.display()));
+                    continue;
+                }
+            };
+            for p in [1, 2] {
+                if example.answers.get(day.day, p).is_none() {
+                    continue;
+                }
+                match example.answers.check(day.day, p, &day.part(p, &parsed)) {
+                    Verdict::Pass => checked += 1,
+                    verdict => {
+                        failures.push(format!("{} part {p}: {verdict}", example.path.display()))
+                    }
+                }
+            }
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    assert!(checked > 0, "no examples found");
 }
@@ -0,0 +1,170 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::{self, Display, Formatter};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A global allocator that wraps `System` and, once `enable`d, counts each thread's allocations.
+/// Install it in a binary with
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy, Default)]
+struct Counters {
+    allocations: u64,
+    bytes: u64,
+    live: i64,
+    peak: i64,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters { allocations: 0, bytes: 0, live: 0, peak: 0 })
+    };
+}
+
+/// Records `allocated` new bytes and a change of `delta` in live bytes against the current thread.
+fn record(allocated: Option<usize>, delta: i64) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    // fails only while the thread is being torn down
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        if let Some(bytes) = allocated {
+            c.allocations += 1;
+            c.bytes += bytes as u64;
+        }
+        c.live += delta;
+        c.peak = c.peak.max(c.live);
+        counters.set(c);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record(Some(layout.size()), layout.size() as i64);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record(Some(layout.size()), layout.size() as i64);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record(None, -(layout.size() as i64));
+    }
+
+    /// Counted as a new allocation of `new_size` bytes, since that's what growing a `Vec` costs.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            record(Some(new_size), new_size as i64 - layout.size() as i64);
+        }
+        new_ptr
+    }
+}
+
+/// Starts counting allocations. Returns false, leaving counting off, if `CountingAllocator` isn't
+/// the global allocator.
+pub fn enable() -> bool {
+    ENABLED.store(true, Ordering::Relaxed);
+    let (_, stats) = measure(|| black_box(Box::new(0u64)));
+    let installed = stats.is_some_and(|stats| stats.allocations > 0);
+    ENABLED.store(installed, Ordering::Relaxed);
+    installed
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// What a piece of code allocated on the thread that ran it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    /// The most memory live at once, counting only what was allocated during the measurement.
+    pub peak: u64,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+/// `bytes` in the largest binary unit that keeps it at least 1, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// Runs `f`, returning what it allocated on this thread, or `None` if counting isn't enabled.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !enabled() {
+        return (f(), None);
+    }
+    let saved = COUNTERS.with(|counters| counters.replace(Counters::default()));
+    let result = f();
+    let c = COUNTERS.with(|counters| counters.replace(saved));
+    let stats = AllocStats {
+        allocations: c.allocations,
+        bytes: c.bytes,
+        peak: c.peak.max(0) as u64,
+    };
+    (result, Some(stats))
+}
+
+#[cfg(test)]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_measure() {
+    assert!(enable());
+    let (len, stats) = measure(|| {
+        let mut v: Vec<u64> = Vec::with_capacity(1000);
+        v.extend(0..1000);
+        let w = v.clone();
+        drop(v);
+        w.len()
+    });
+    assert_eq!(len, 1000);
+    let stats = stats.unwrap();
+    assert_eq!(stats.allocations, 2);
+    assert_eq!(stats.bytes, 16000);
+    assert_eq!(stats.peak, 16000);
+    assert_eq!(format_bytes(16000), "15.6 KiB");
+    assert_eq!(format_bytes(12), "12 B");
+}
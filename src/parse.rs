@@ -0,0 +1,137 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// A parse failure, located by line and column (both 1-based) in the puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    /// The offending text, empty if the line or the input ended early.
+    pub found: String,
+    /// Whether the input ended early, in which case the error points just past its last line.
+    pub end_of_input: bool,
+    /// The whole input line containing the error, for rendering.
+    pub source_line: String,
+}
+
+impl ParseError {
+    /// Builds an error pointing at `found`, which must be a slice of `input`. An empty `found` at
+    /// the very end of `input` (see `end_of`) is reported as the end of the input, at the end of
+    /// its last line rather than on the empty line after the final newline.
+    pub fn at(input: &str, found: &str, expected: impl Into<String>) -> ParseError {
+        let start = input.as_ptr() as usize;
+        let mut offset = (found.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset + found.len() <= input.len())
+            .expect("found text is not a slice of the input");
+        let end_of_input = found.is_empty() && offset == input.len();
+        if end_of_input {
+            offset = input.trim_end_matches(['\r', '\n']).len();
+        }
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        ParseError {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            expected: expected.into(),
+            found: found.to_string(),
+            end_of_input,
+            source_line: input[line_start..line_end].to_string(),
+        }
+    }
+
+    /// The error followed by the offending line, with the bad text underlined.
+    pub fn render(&self) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        let underline = "^".repeat(
+            self.found
+                .lines()
+                .next()
+                .unwrap_or("")
+                .chars()
+                .count()
+                .max(1),
+        );
+        format!(
+            "error: {self}\n{gutter} |\n{} | {}\n{gutter} | {}{underline}",
+            self.line,
+            self.source_line,
+            " ".repeat(self.column - 1),
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.end_of_input {
+            write!(f, "end of input")
+        } else if self.found.is_empty() {
+            write!(f, "end of line")
+        } else {
+            write!(f, "{:?}", self.found)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `token`, a slice of `input`, reporting where it is if it isn't a valid `T`.
+pub fn parse_at<T: FromStr>(input: &str, token: &str, expected: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(input, token, expected))
+}
+
+/// The empty slice at the end of `s`, to point at when something is missing.
+pub fn end_of(s: &str) -> &str {
+    &s[s.len()..]
+}
+
+#[test]
+fn test_parse_error() {
+    let input = "3   4\n4   x3\n";
+    let line = input.lines().nth(1).unwrap();
+    let err = parse_at::<i32>(input, &line[4..], "integer").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 5));
+    assert_eq!(err.found, "x3");
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 5: expected integer, found \"x3\""
+    );
+    assert_eq!(
+        err.render(),
+        "error: line 2, column 5: expected integer, found \"x3\"
+  |
+2 | 4   x3
+  |     ^^"
+    );
+
+    let err = ParseError::at(input, end_of(line), "integer");
+    assert_eq!((err.line, err.column), (2, 7));
+    assert!(err.to_string().ends_with("found end of line"));
+    assert!(err.render().ends_with("|       ^"));
+
+    // truncated input points past the end of the last line, not at the line after it
+    let err = ParseError::at(input, end_of(input), "integer");
+    assert_eq!((err.line, err.column), (2, 7));
+    assert!(err.end_of_input);
+    assert_eq!(
+        err.render(),
+        "error: line 2, column 7: expected integer, found end of input
+  |
+2 | 4   x3
+  |       ^"
+    );
+    let err = ParseError::at("", "", "integer");
+    assert_eq!((err.line, err.column), (1, 1));
+    assert!(err.to_string().ends_with("found end of input"));
+}
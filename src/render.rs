@@ -0,0 +1,174 @@
+use crate::{Grid, Vec2};
+use std::collections::HashSet;
+use std::env;
+use std::fmt::{self, Display, Formatter};
+use std::io::{stdout, IsTerminal};
+
+/// The ANSI foreground colours an overlay can be drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    fn code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+        }
+    }
+}
+
+/// A set of positions to pick out when rendering a grid, by colouring them, drawing them with a
+/// different glyph, or both.
+#[derive(Debug, Clone)]
+pub struct Overlay {
+    positions: HashSet<Vec2<isize>>,
+    color: Option<Color>,
+    glyph: Option<char>,
+}
+
+impl Overlay {
+    pub fn new(positions: impl IntoIterator<Item = Vec2<isize>>) -> Overlay {
+        Overlay {
+            positions: positions.into_iter().collect(),
+            color: None,
+            glyph: None,
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Overlay {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn glyph(mut self, glyph: char) -> Overlay {
+        self.glyph = Some(glyph);
+        self
+    }
+}
+
+/// A grid drawn with its overlays, shown through `Display`. Each cell takes its glyph and colour
+/// from the last overlay covering it that sets one, so later overlays draw on top.
+pub struct Render<'a, T> {
+    grid: &'a Grid<T>,
+    overlays: Vec<Overlay>,
+    color: bool,
+}
+
+impl<T> Grid<T> {
+    /// Starts rendering the grid for printing to stdout: colour is on only when stdout is a
+    /// terminal and `NO_COLOR` isn't set, so redirected output stays plain text. Use `color_for`
+    /// when writing somewhere else, such as the stderr that `debug!` and `trace!` log to.
+    pub fn render(&self) -> Render<'_, T> {
+        Render {
+            grid: self,
+            overlays: Vec::new(),
+            color: false,
+        }
+        .color_for(&stdout())
+    }
+}
+
+impl<T> Render<'_, T> {
+    pub fn overlay(mut self, overlay: Overlay) -> Self {
+        self.overlays.push(overlay);
+        self
+    }
+
+    /// Uses colour only if `stream`, where the rendering will be written, is a terminal and
+    /// `NO_COLOR` isn't set.
+    pub fn color_for(self, stream: &impl IsTerminal) -> Self {
+        self.color(stream.is_terminal() && env::var_os("NO_COLOR").is_none())
+    }
+
+    /// Forces colour on or off, whatever the output is.
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl<T: Copy + Into<char>> Display for Render<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.grid.rows().enumerate() {
+            let mut current = None;
+            for (x, &cell) in row.iter().enumerate() {
+                let pos = Vec2 {
+                    x: x as isize,
+                    y: y as isize,
+                };
+                let covering = || {
+                    self.overlays
+                        .iter()
+                        .rev()
+                        .filter(|overlay| overlay.positions.contains(&pos))
+                };
+                let glyph = covering().find_map(|overlay| overlay.glyph);
+                let color = covering().find_map(|overlay| overlay.color);
+                if self.color && color != current {
+                    match color {
+                        Some(color) => write!(f, "\x1b[{}m", color.code())?,
+                        None => write!(f, "\x1b[0m")?,
+                    }
+                    current = color;
+                }
+                write!(f, "{}", glyph.unwrap_or(cell.into()))?;
+            }
+            if current.is_some() {
+                write!(f, "\x1b[0m")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_render() {
+    let grid = Grid::from("abc\ndef\n");
+    let path = Overlay::new([
+        Vec2 { x: 0, y: 0 },
+        Vec2 { x: 1, y: 0 },
+        Vec2 { x: 1, y: 1 },
+    ])
+    .color(Color::Yellow);
+    let robot = Overlay::new([Vec2 { x: 1, y: 0 }]).glyph('@');
+    assert_eq!(grid.render().color(false).to_string(), grid.to_string());
+    // a file is never a terminal, so rendering for one is plain text whatever came before
+    let file = std::fs::File::open(file!()).unwrap();
+    assert_eq!(
+        grid.render()
+            .overlay(path.clone())
+            .color(true)
+            .color_for(&file)
+            .to_string(),
+        grid.to_string()
+    );
+    assert_eq!(
+        grid.render()
+            .overlay(path.clone())
+            .overlay(robot.clone())
+            .color(false)
+            .to_string(),
+        "a@c\ndef\n"
+    );
+    assert_eq!(
+        grid.render()
+            .overlay(path)
+            .overlay(robot)
+            .overlay(Overlay::new([Vec2 { x: 2, y: 1 }]).color(Color::Red))
+            .color(true)
+            .to_string(),
+        "\x1b[33ma@\x1b[0mc\nd\x1b[33me\x1b[31mf\x1b[0m\n"
+    );
+}
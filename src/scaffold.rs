@@ -0,0 +1,307 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Creates a new day under `root` (the crate root): `src/bin/dayNN.rs` from `src/template`, an
+/// empty `inputs/inputNN.txt`, an empty example in `examples/dayNN/` to paste the puzzle's example
+/// and answers into, entries in the runner's dispatch table in `src/main.rs`, and the day's binary
+/// in `Cargo.toml`. Nothing is
+/// written if any of those already exist or the day is already registered, and if a write fails
+/// the files already created are removed, so it can simply be run again. Returns the files
+/// created.
+pub fn new_day(root: &Path, day: u32) -> io::Result<Vec<PathBuf>> {
+    let source_path = root.join(format!("src/bin/day{day:02}.rs"));
+    let input_path = root.join(format!("inputs/input{day:02}.txt"));
+    let examples_dir = root.join(format!("examples/day{day:02}"));
+    let example_path = examples_dir.join("example.txt");
+    let answers_path = examples_dir.join("example.answers");
+    let main_path = root.join("src/main.rs");
+    for path in [&source_path, &input_path, &example_path, &answers_path] {
+        if path.exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", path.display()),
+            ));
+        }
+    }
+    let template = fs::read_to_string(root.join("src/template"))?;
+    let main_rs = register(&fs::read_to_string(&main_path)?, day)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let cargo_path = root.join("Cargo.toml");
+    let old_cargo_toml = fs::read_to_string(&cargo_path)?;
+    let cargo_toml = register_bin(&old_cargo_toml, day)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+    let files = [
+        (source_path, fill_template(&template, day)),
+        (input_path, String::new()),
+        (example_path, String::new()),
+        (answers_path, EXAMPLE_ANSWERS.to_string()),
+    ];
+    let mut created = vec![];
+    let result = files
+        .into_iter()
+        .try_for_each(|(path, contents)| {
+            fs::create_dir_all(path.parent().expect("scaffolded files are in a directory"))?;
+            fs::write(&path, contents)?;
+            created.push(path);
+            Ok(())
+        })
+        .and_then(|()| fs::write(&cargo_path, cargo_toml))
+        // main.rs goes last: once it's written, the day is registered and complete
+        .and_then(|()| fs::write(&main_path, main_rs));
+    if let Err(err) = result {
+        for path in &created {
+            let _ = fs::remove_file(path);
+        }
+        let _ = fs::write(&cargo_path, old_cargo_toml);
+        // only succeeds if it's empty, so an examples directory with anything else in it stays
+        let _ = fs::remove_dir(&examples_dir);
+        return Err(err);
+    }
+    Ok(created)
+}
+
+/// A new example's answers file, with nothing checked until the answers are filled in.
+const EXAMPLE_ANSWERS: &str = "# <part> <answer>, one per line, e.g. `1 42`\n";
+
+fn fill_template(template: &str, day: u32) -> String {
+    template
+        .replace("DayNN", &format!("Day{day:02}"))
+        .replace("dayNN", &format!("day{day:02}"))
+        .replace("NN", &day.to_string())
+}
+
+/// Adds `day`'s binary to `Cargo.toml`, with its tests turned off because they already run in the
+/// runner's, keeping the entries in order.
+fn register_bin(cargo_toml: &str, day: u32) -> Result<String, String> {
+    let name = format!("name = \"day{day:02}\"");
+    // entries are separated by blank lines and look like `[[bin]]\nname = "day07"\ntest = false`
+    let day_of = |section: &str| -> Option<u32> {
+        let line = section
+            .lines()
+            .find(|line| line.starts_with("name = \"day"))?;
+        line.get(11..13)?.parse().ok()
+    };
+    let mut sections: Vec<&str> = cargo_toml.split("\n\n").collect();
+    if sections
+        .iter()
+        .any(|section| section.lines().any(|line| line == name))
+    {
+        return Err(format!("day{day:02} is already in Cargo.toml"));
+    }
+    let days: Vec<(usize, u32)> = sections
+        .iter()
+        .enumerate()
+        .filter_map(|(i, section)| Some((i, day_of(section)?)))
+        .collect();
+    let at = match days.iter().find(|&&(_, d)| d > day) {
+        Some(&(i, _)) => i,
+        None => {
+            days.last()
+                .ok_or("could not find day binaries in Cargo.toml")?
+                .0
+                + 1
+        }
+    };
+    let entry = format!("[[bin]]\n{name}\ntest = false");
+    sections.insert(at, &entry);
+    Ok(sections.join("\n\n"))
+}
+
+/// Adds `day` to the runner's module list and `DAYS` table, keeping both in order.
+fn register(main_rs: &str, day: u32) -> Result<String, String> {
+    let module = format!("day{day:02}");
+    if main_rs.contains(&format!("mod {module};")) {
+        return Err(format!("{module} is already registered in src/main.rs"));
+    }
+    let mut lines: Vec<String> = main_rs.lines().map(str::to_string).collect();
+
+    // registered days look like `mod day07;` and `    Day::of::<day07::Day07>(),`
+    let day_of = |line: &str, prefix: &str| -> Option<u32> {
+        line.strip_prefix(prefix)?.get(0..2)?.parse().ok()
+    };
+    let last_before = |lines: &[String], prefix: &str| {
+        let entries: Vec<(usize, u32)> = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| Some((i, day_of(line, prefix)?)))
+            .collect();
+        match entries.iter().rev().find(|(_, d)| *d < day) {
+            Some(&(i, _)) => Some(i + 1),
+            None => entries.first().map(|&(i, _)| i),
+        }
+    };
+
+    let table_at = last_before(&lines, "    Day::of::<day")
+        .ok_or("could not find the DAYS table in src/main.rs")?;
+    lines.insert(table_at, format!("    Day::of::<{module}::Day{day:02}>(),"));
+
+    let mod_at =
+        match last_before(&lines, "mod day").ok_or("could not find day modules in src/main.rs")? {
+            // insert before the attributes of the following module, not between them
+            i if lines.get(i).is_some_and(|line| line.starts_with("mod day")) => i - 2,
+            i => i,
+        };
+    for (offset, line) in [
+        "#[allow(dead_code)]".to_string(),
+        format!("#[path = \"bin/{module}.rs\"]"),
+        format!("mod {module};"),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        lines.insert(mod_at + offset, line);
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+static TEST_MAIN: &str = "use advent_of_code_2024::Day;
+
+#[allow(dead_code)]
+#[path = \"bin/day01.rs\"]
+mod day01;
+#[allow(dead_code)]
+#[path = \"bin/day03.rs\"]
+mod day03;
+
+const DAYS: &[Day] = &[
+    Day::of::<day01::Day01>(),
+    Day::of::<day03::Day03>(),
+];
+";
+
+#[test]
+fn test_register() {
+    let main_rs = register(TEST_MAIN, 2).unwrap();
+    assert!(main_rs.contains(
+        "mod day01;
+#[allow(dead_code)]
+#[path = \"bin/day02.rs\"]
+mod day02;
+#[allow(dead_code)]
+#[path = \"bin/day03.rs\"]
+mod day03;"
+    ));
+    assert!(main_rs.contains(
+        "    Day::of::<day01::Day01>(),
+    Day::of::<day02::Day02>(),
+    Day::of::<day03::Day03>(),"
+    ));
+
+    let main_rs = register(TEST_MAIN, 18).unwrap();
+    assert!(main_rs
+        .contains("mod day03;\n#[allow(dead_code)]\n#[path = \"bin/day18.rs\"]\nmod day18;\n\n"));
+    assert!(main_rs.contains("    Day::of::<day18::Day18>(),\n];"));
+
+    let without_day01 = TEST_MAIN
+        .replace(
+            "#[allow(dead_code)]\n#[path = \"bin/day01.rs\"]\nmod day01;\n",
+            "",
+        )
+        .replace("    Day::of::<day01::Day01>(),\n", "");
+    assert_eq!(register(&without_day01, 1).unwrap(), TEST_MAIN);
+
+    assert!(register(TEST_MAIN, 3).is_err());
+    assert!(register("fn main() {}", 3).is_err());
+}
+
+#[cfg(test)]
+static TEST_CARGO: &str = "[[bin]]
+name = \"aoc\"
+path = \"src/main.rs\"
+
+[[bin]]
+name = \"day01\"
+test = false
+
+[[bin]]
+name = \"day03\"
+test = false
+
+[dependencies]
+";
+
+#[test]
+fn test_register_bin() {
+    let entry = |day: &str| format!("[[bin]]\nname = \"{day}\"\ntest = false\n\n");
+    let cargo_toml = register_bin(TEST_CARGO, 2).unwrap();
+    assert!(cargo_toml.contains(&[entry("day01"), entry("day02"), entry("day03")].concat()));
+    let cargo_toml = register_bin(TEST_CARGO, 18).unwrap();
+    assert!(cargo_toml.contains(&(entry("day18") + "[dependencies]\n")));
+    assert!(cargo_toml.starts_with("[[bin]]\nname = \"aoc\""));
+    assert!(register_bin(TEST_CARGO, 3).is_err());
+    assert!(register_bin("[package]\n", 3).is_err());
+}
+
+#[test]
+fn test_fill_template() {
+    assert_eq!(
+        fill_template(
+            "// examples/dayNN\npub struct DayNN;\nconst DAY: u32 = NN;",
+            5
+        ),
+        "// examples/day05\npub struct Day05;\nconst DAY: u32 = 5;"
+    );
+}
+
+#[test]
+fn test_new_day() {
+    let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+    fs::create_dir_all(root.join("src/bin")).unwrap();
+    fs::write(root.join("src/template"), "pub struct DayNN;\n").unwrap();
+    fs::write(root.join("src/main.rs"), TEST_MAIN).unwrap();
+    fs::write(root.join("Cargo.toml"), TEST_CARGO).unwrap();
+
+    let created = new_day(&root, 2).unwrap();
+    assert_eq!(created.len(), 4);
+    assert_eq!(
+        fs::read_to_string(root.join("src/bin/day02.rs")).unwrap(),
+        "pub struct Day02;\n"
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("inputs/input02.txt")).unwrap(),
+        ""
+    );
+    let examples = crate::fixtures::discover(&root.join("examples/day02"), 2).unwrap();
+    assert_eq!(examples.len(), 1);
+    assert_eq!(examples[0].input, "");
+    assert_eq!(examples[0].answers.get(2, 1), None);
+    assert!(fs::read_to_string(root.join("src/main.rs"))
+        .unwrap()
+        .contains("mod day02;"));
+    assert!(fs::read_to_string(root.join("Cargo.toml"))
+        .unwrap()
+        .contains("name = \"day02\""));
+
+    let err = new_day(&root, 2).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+
+    // a day that's already registered is refused before anything is written
+    let registered = fs::read_to_string(root.join("src/main.rs")).unwrap();
+    assert!(new_day(&root, 1).is_err());
+    assert!(!root.join("src/bin/day01.rs").exists());
+    assert!(!root.join("examples/day01").exists());
+    assert_eq!(
+        fs::read_to_string(root.join("src/main.rs")).unwrap(),
+        registered
+    );
+
+    // a file where the examples directory should be makes a write fail partway through, which
+    // must leave nothing behind, so the day can be created once it's out of the way
+    fs::write(root.join("examples/day04"), "").unwrap();
+    assert!(new_day(&root, 4).is_err());
+    assert!(!root.join("src/bin/day04.rs").exists());
+    assert!(!root.join("inputs/input04.txt").exists());
+    assert!(!fs::read_to_string(root.join("src/main.rs"))
+        .unwrap()
+        .contains("mod day04;"));
+    assert!(!fs::read_to_string(root.join("Cargo.toml"))
+        .unwrap()
+        .contains("day04"));
+    fs::remove_file(root.join("examples/day04")).unwrap();
+    assert_eq!(new_day(&root, 4).unwrap().len(), 4);
+    fs::remove_dir_all(&root).unwrap();
+}
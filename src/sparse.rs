@@ -0,0 +1,197 @@
+use crate::{Grid, Rect, Vec2};
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+
+/// A grid with no fixed size: cells can be set anywhere, including at negative coordinates, and
+/// the bounds grow and shrink to fit them. Cells never set are empty.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Vec2<isize>, T>,
+    bounds: Option<Rect>,
+    /// Drawn for empty cells by `Display`.
+    pub fill: char,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+            fill: '.',
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, pos: Vec2<isize>) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Vec2<isize>) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    pub fn contains(&self, pos: Vec2<isize>) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    /// Sets a cell, returning what was there before.
+    pub fn insert(&mut self, pos: Vec2<isize>, value: T) -> Option<T> {
+        self.extend_bounds(pos);
+        self.cells.insert(pos, value)
+    }
+
+    /// Empties a cell, returning what was there.
+    pub fn remove(&mut self, pos: Vec2<isize>) -> Option<T> {
+        let value = self.cells.remove(&pos)?;
+        // the bounds can only shrink if the cell was on their edge
+        let on_edge = self.bounds.is_some_and(|b| {
+            pos.x == b.x
+                || pos.y == b.y
+                || pos.x == b.x + b.w as isize - 1
+                || pos.y == b.y + b.h as isize - 1
+        });
+        if on_edge {
+            self.bounds = None;
+            let positions: Vec<Vec2<isize>> = self.cells.keys().copied().collect();
+            for pos in positions {
+                self.extend_bounds(pos);
+            }
+        }
+        Some(value)
+    }
+
+    fn extend_bounds(&mut self, pos: Vec2<isize>) {
+        self.bounds = Some(match self.bounds {
+            None => Rect {
+                x: pos.x,
+                y: pos.y,
+                w: 1,
+                h: 1,
+            },
+            Some(b) => {
+                let (x, y) = (b.x.min(pos.x), b.y.min(pos.y));
+                let right = (b.x + b.w as isize).max(pos.x + 1);
+                let bottom = (b.y + b.h as isize).max(pos.y + 1);
+                Rect {
+                    x,
+                    y,
+                    w: (right - x) as usize,
+                    h: (bottom - y) as usize,
+                }
+            }
+        });
+    }
+
+    /// The smallest rectangle holding every cell that's set, or `None` if none are.
+    pub fn bounds(&self) -> Option<Rect> {
+        self.bounds
+    }
+
+    /// The cells that are set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2<isize>, &T)> {
+        self.cells.iter().map(|(&pos, value)| (pos, value))
+    }
+
+    /// A dense copy of the bounds, with `empty` in the cells that aren't set. Cell `(0, 0)` of the
+    /// result is the top left of `bounds()`.
+    pub fn to_grid(&self, empty: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let Some(bounds) = self.bounds else {
+            return Grid::of(empty, 0, 0);
+        };
+        let mut grid = Grid::of(empty, bounds.w, bounds.h);
+        let origin = Vec2 {
+            x: bounds.x,
+            y: bounds.y,
+        };
+        for (&pos, value) in &self.cells {
+            grid[pos - origin] = value.clone();
+        }
+        grid
+    }
+}
+
+impl<T: Clone> From<&Grid<T>> for SparseGrid<T> {
+    /// Every cell of the grid, at the same positions.
+    fn from(grid: &Grid<T>) -> Self {
+        let mut sparse = SparseGrid::new();
+        for (y, row) in grid.rows().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                let pos = Vec2::try_from((x, y)).expect("grid too large");
+                sparse.insert(pos, value.clone());
+            }
+        }
+        sparse
+    }
+}
+
+impl<T: Copy + Into<char>> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Some(bounds) = self.bounds else {
+            return Ok(());
+        };
+        for y in bounds.y..bounds.y + bounds.h as isize {
+            let line: String = (bounds.x..bounds.x + bounds.w as isize)
+                .map(|x| self.get(Vec2 { x, y }).map_or(self.fill, |&c| c.into()))
+                .collect();
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_sparse_grid() {
+    let mut sparse: SparseGrid<u8> = SparseGrid::new();
+    assert_eq!(sparse.bounds(), None);
+    assert_eq!(sparse.to_string(), "");
+    sparse.insert(Vec2 { x: -1, y: 2 }, b'#');
+    sparse.insert(Vec2 { x: 1, y: 0 }, b'@');
+    assert_eq!(
+        sparse.bounds(),
+        Some(Rect {
+            x: -1,
+            y: 0,
+            w: 3,
+            h: 3
+        })
+    );
+    assert_eq!(sparse.to_string(), "..@\n...\n#..\n");
+    sparse.fill = ' ';
+    assert_eq!(sparse.to_string(), "  @\n   \n#  \n");
+
+    let grid = sparse.to_grid(b'.');
+    assert_eq!(grid.to_string(), "..@\n...\n#..\n");
+    let round_trip = SparseGrid::from(&grid);
+    assert_eq!(round_trip.len(), 9);
+    assert_eq!(round_trip.get(Vec2 { x: 2, y: 0 }), Some(&b'@'));
+
+    assert_eq!(sparse.remove(Vec2 { x: -1, y: 2 }), Some(b'#'));
+    assert_eq!(
+        sparse.bounds(),
+        Some(Rect {
+            x: 1,
+            y: 0,
+            w: 1,
+            h: 1
+        })
+    );
+    sparse.remove(Vec2 { x: 1, y: 0 });
+    assert!(sparse.is_empty());
+    assert_eq!(sparse.bounds(), None);
+}
@@ -0,0 +1,104 @@
+use crate::{Rect, Vec2};
+
+/// A `w` by `h` board whose opposite edges join up, so anything moving off one side comes back
+/// on the other. Positions outside the board stand for the cell they wrap round to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Torus {
+    w: usize,
+    h: usize,
+}
+
+impl Torus {
+    pub const fn new(w: usize, h: usize) -> Torus {
+        assert!(w > 0 && h > 0, "a torus must have at least one cell");
+        Torus { w, h }
+    }
+
+    pub fn width(&self) -> usize {
+        self.w
+    }
+
+    pub fn height(&self) -> usize {
+        self.h
+    }
+
+    fn size(&self) -> Vec2<isize> {
+        Vec2 {
+            x: self.w as isize,
+            y: self.h as isize,
+        }
+    }
+
+    /// The cell on the board that `pos` wraps round to.
+    pub fn wrap(&self, pos: Vec2<isize>) -> Vec2<isize> {
+        pos.rem_euclid(self.size())
+    }
+
+    /// Every cell on the board, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = Vec2<isize>> {
+        let size = self.size();
+        (0..size.y).flat_map(move |y| (0..size.x).map(move |x| Vec2 { x, y }))
+    }
+
+    /// Which quarter of the board `pos` wraps into: 0 top left, 1 top right, 2 bottom left and
+    /// 3 bottom right. When a side has odd length its middle line belongs to no quadrant.
+    pub fn quadrant(&self, pos: Vec2<isize>) -> Option<usize> {
+        let pos = self.wrap(pos);
+        let half = |p: isize, len: usize| {
+            let len = len as isize;
+            if p < len / 2 {
+                Some(0)
+            } else if p >= (len + 1) / 2 {
+                Some(1)
+            } else {
+                None
+            }
+        };
+        Some(half(pos.y, self.h)? * 2 + half(pos.x, self.w)?)
+    }
+
+    /// How many of `positions` are in each quadrant, in the order given by `quadrant`.
+    pub fn quadrant_counts(&self, positions: impl IntoIterator<Item = Vec2<isize>>) -> [usize; 4] {
+        let mut counts = [0; 4];
+        for pos in positions {
+            if let Some(q) = self.quadrant(pos) {
+                counts[q] += 1;
+            }
+        }
+        counts
+    }
+
+    pub fn rect(&self) -> Rect {
+        Rect {
+            x: 0,
+            y: 0,
+            w: self.w,
+            h: self.h,
+        }
+    }
+}
+
+#[test]
+fn test_torus() {
+    let torus = Torus::new(11, 7);
+    assert_eq!((torus.width(), torus.height()), (11, 7));
+    assert_eq!(torus.wrap(Vec2 { x: -1, y: 15 }), Vec2 { x: 10, y: 1 });
+    assert_eq!(torus.cells().count(), 77);
+    assert_eq!(torus.cells().nth(12), Some(Vec2 { x: 1, y: 1 }));
+    assert!(torus.cells().all(|cell| torus.rect().contains(cell)));
+
+    assert_eq!(torus.quadrant(Vec2 { x: 4, y: 2 }), Some(0));
+    assert_eq!(torus.quadrant(Vec2 { x: 6, y: 0 }), Some(1));
+    assert_eq!(torus.quadrant(Vec2 { x: 0, y: 4 }), Some(2));
+    assert_eq!(torus.quadrant(Vec2 { x: -1, y: -1 }), Some(3));
+    assert_eq!(torus.quadrant(Vec2 { x: 5, y: 0 }), None);
+    assert_eq!(torus.quadrant(Vec2 { x: 0, y: 3 }), None);
+    assert_eq!(Torus::new(4, 2).quadrant(Vec2 { x: 2, y: 1 }), Some(3));
+
+    let counts = torus.quadrant_counts([
+        Vec2 { x: 0, y: 0 },
+        Vec2 { x: 1, y: 1 },
+        Vec2 { x: 5, y: 5 },
+    ]);
+    assert_eq!(counts, [2, 0, 0, 0]);
+}
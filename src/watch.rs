@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Polls files and directories for changes by comparing modification times, so no platform
+/// notification API is needed.
+pub struct Watcher {
+    files: Vec<PathBuf>,
+    dirs: Vec<PathBuf>,
+    snapshot: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Watcher {
+    /// Watches each of `files`, and every file directly inside each of `dirs`, including ones
+    /// added later. Paths that don't exist yet are fine; creating them counts as a change.
+    pub fn new(files: Vec<PathBuf>, dirs: Vec<PathBuf>) -> Watcher {
+        let mut watcher = Watcher {
+            files,
+            dirs,
+            snapshot: vec![],
+        };
+        watcher.snapshot = watcher.take_snapshot();
+        watcher
+    }
+
+    fn take_snapshot(&self) -> Vec<(PathBuf, Option<SystemTime>)> {
+        let mut paths = self.files.clone();
+        for dir in &self.dirs {
+            if let Ok(entries) = fs::read_dir(dir) {
+                let mut entries: Vec<PathBuf> = entries
+                    .filter_map(|entry| Some(entry.ok()?.path()))
+                    .collect();
+                entries.sort();
+                paths.extend(entries);
+            }
+        }
+        paths
+            .into_iter()
+            .map(|path| {
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                (path, modified)
+            })
+            .collect()
+    }
+
+    /// Whether anything was modified, created or deleted since the last call (or since the
+    /// watcher was made).
+    pub fn changed(&mut self) -> bool {
+        let snapshot = self.take_snapshot();
+        let changed = snapshot != self.snapshot;
+        self.snapshot = snapshot;
+        changed
+    }
+}
+
+#[test]
+fn test_watcher() {
+    use std::time::Duration;
+
+    let root = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+    let dir = root.join("examples");
+    fs::create_dir_all(&dir).unwrap();
+    let input = root.join("input.txt");
+    fs::write(&input, "1\n").unwrap();
+
+    let mut watcher = Watcher::new(vec![input.clone()], vec![dir.clone()]);
+    assert!(!watcher.changed());
+
+    let file = fs::File::options().write(true).open(&input).unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_secs(60))
+        .unwrap();
+    assert!(watcher.changed());
+    assert!(!watcher.changed());
+
+    fs::write(dir.join("example.txt"), "2\n").unwrap();
+    assert!(watcher.changed());
+    fs::remove_file(&input).unwrap();
+    assert!(watcher.changed());
+    fs::remove_dir_all(&root).unwrap();
+}